@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const MIN_CODE_EXPIRATION_MINUTES: u32 = 1;
+pub const MAX_CODE_EXPIRATION_MINUTES: u32 = 90;
+
+const DEFAULT_COPY_CODE_TEXT: &str = "Copy code";
+const DEFAULT_AUTOFILL_TEXT: &str = "Autofill";
+const EXAMPLE_CODE: &str = "123456";
+
+/// The OTP button every AUTHENTICATION template must carry.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "otp_type")]
+pub enum OtpButton {
+    #[serde(rename = "COPY_CODE")]
+    CopyCode { text: Option<String> },
+    /// Android one-tap autofill, which falls back to copy-code on other devices.
+    #[serde(rename = "ONE_TAP")]
+    OneTap {
+        text: Option<String>,
+        autofill_text: Option<String>,
+        package_name: String,
+        signature_hash: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuthenticationOptions {
+    pub otp_button: OtpButton,
+    #[serde(default)]
+    pub add_security_recommendation: bool,
+    pub code_expiration_minutes: Option<u32>,
+}
+
+impl AuthenticationOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(minutes) = self.code_expiration_minutes {
+            if !(MIN_CODE_EXPIRATION_MINUTES..=MAX_CODE_EXPIRATION_MINUTES).contains(&minutes) {
+                return Err(format!(
+                    "Code expiration must be between {} and {} minutes",
+                    MIN_CODE_EXPIRATION_MINUTES, MAX_CODE_EXPIRATION_MINUTES
+                ));
+            }
+        }
+
+        if let OtpButton::OneTap { package_name, signature_hash, .. } = &self.otp_button {
+            if package_name.trim().is_empty() {
+                return Err("One-tap OTP buttons need the Android package name".to_string());
+            }
+            if signature_hash.len() != 11 {
+                return Err("One-tap OTP buttons need the 11-character app signature hash".to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the OTP button into the JSON string Gupshup expects in the `buttons` form field.
+    pub fn buttons_form_value(&self) -> String {
+        let button = match &self.otp_button {
+            OtpButton::CopyCode { text } => json!({
+                "type": "OTP",
+                "otp_type": "COPY_CODE",
+                "text": text.as_deref().unwrap_or(DEFAULT_COPY_CODE_TEXT),
+            }),
+            OtpButton::OneTap { text, autofill_text, package_name, signature_hash } => json!({
+                "type": "OTP",
+                "otp_type": "ONE_TAP",
+                "text": text.as_deref().unwrap_or(DEFAULT_COPY_CODE_TEXT),
+                "autofill_text": autofill_text.as_deref().unwrap_or(DEFAULT_AUTOFILL_TEXT),
+                "package_name": package_name,
+                "signature_hash": signature_hash,
+            }),
+        };
+        Value::Array(vec![button]).to_string()
+    }
+}
+
+/// Fills the code placeholder of an authentication body so it can be sent as the example.
+pub fn example_for(content: &str) -> String {
+    content.replace("{{1}}", EXAMPLE_CODE)
+}
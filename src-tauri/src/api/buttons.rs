@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const MAX_BUTTONS: usize = 10;
+pub const MAX_URL_BUTTONS: usize = 2;
+pub const MAX_PHONE_NUMBER_BUTTONS: usize = 1;
+pub const MAX_COPY_CODE_BUTTONS: usize = 1;
+pub const MAX_BUTTON_TEXT_LENGTH: usize = 25;
+pub const MAX_URL_LENGTH: usize = 2000;
+pub const MAX_PHONE_NUMBER_LENGTH: usize = 20;
+pub const MAX_COPY_CODE_LENGTH: usize = 15;
+
+const URL_VARIABLE: &str = "{{1}}";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum TemplateButton {
+    #[serde(rename = "QUICK_REPLY")]
+    QuickReply { text: String },
+    /// A static URL, or a dynamic one ending in `{{1}}` with an example of the full URL.
+    #[serde(rename = "URL")]
+    Url {
+        text: String,
+        url: String,
+        example: Option<String>,
+    },
+    #[serde(rename = "PHONE_NUMBER")]
+    PhoneNumber { text: String, phone_number: String },
+    #[serde(rename = "COPY_CODE")]
+    CopyCode { example: String },
+}
+
+impl TemplateButton {
+    fn is_quick_reply(&self) -> bool {
+        matches!(self, TemplateButton::QuickReply { .. })
+    }
+
+    fn to_gupshup_value(&self) -> Value {
+        match self {
+            TemplateButton::QuickReply { text } => json!({
+                "type": "QUICK_REPLY",
+                "text": text,
+            }),
+            TemplateButton::Url { text, url, example } => match example {
+                Some(example) if url.ends_with(URL_VARIABLE) => json!({
+                    "type": "URL",
+                    "text": text,
+                    "url": url,
+                    "example": [example],
+                }),
+                _ => json!({
+                    "type": "URL",
+                    "text": text,
+                    "url": url,
+                }),
+            },
+            TemplateButton::PhoneNumber { text, phone_number } => json!({
+                "type": "PHONE_NUMBER",
+                "text": text,
+                "phone_number": phone_number,
+            }),
+            TemplateButton::CopyCode { example } => json!({
+                "type": "COPY_CODE",
+                "example": example,
+            }),
+        }
+    }
+}
+
+/// Builds the JSON array Gupshup expects for a list of buttons.
+pub fn to_gupshup_value(buttons: &[TemplateButton]) -> Value {
+    Value::Array(buttons.iter().map(TemplateButton::to_gupshup_value).collect())
+}
+
+/// Serializes the buttons into the JSON string Gupshup expects in the `buttons` form field.
+pub fn to_form_value(buttons: &[TemplateButton]) -> String {
+    to_gupshup_value(buttons).to_string()
+}
+
+fn check_text(text: &str) -> Result<(), String> {
+    if text.trim().is_empty() {
+        return Err("Button text cannot be empty".to_string());
+    }
+    if text.chars().count() > MAX_BUTTON_TEXT_LENGTH {
+        return Err(format!(
+            "Button text '{}' is longer than {} characters",
+            text, MAX_BUTTON_TEXT_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+fn check_url(url: &str, example: &Option<String>) -> Result<(), String> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(format!("Button URL '{}' must start with http:// or https://", url));
+    }
+    if url.chars().count() > MAX_URL_LENGTH {
+        return Err(format!("Button URL is longer than {} characters", MAX_URL_LENGTH));
+    }
+
+    let variables = url.matches("{{").count();
+    if variables == 0 {
+        return Ok(());
+    }
+    if variables > 1 || !url.ends_with(URL_VARIABLE) {
+        return Err(format!(
+            "Button URL '{}' can only have a single {} variable at the end",
+            url, URL_VARIABLE
+        ));
+    }
+
+    let prefix = url.trim_end_matches(URL_VARIABLE);
+    match example {
+        Some(example) if example.starts_with(prefix) && example.len() > prefix.len() => Ok(()),
+        Some(example) => Err(format!(
+            "Button URL example '{}' must be the full URL starting with '{}'",
+            example, prefix
+        )),
+        None => Err(format!("Dynamic button URL '{}' needs an example", url)),
+    }
+}
+
+/// Checks the buttons against WhatsApp's limits on count, grouping and field lengths.
+pub fn validate_buttons(buttons: &[TemplateButton]) -> Result<(), String> {
+    if buttons.len() > MAX_BUTTONS {
+        return Err(format!("A template can have at most {} buttons", MAX_BUTTONS));
+    }
+
+    let count = |predicate: fn(&TemplateButton) -> bool| buttons.iter().filter(|b| predicate(b)).count();
+    if count(|b| matches!(b, TemplateButton::Url { .. })) > MAX_URL_BUTTONS {
+        return Err(format!("A template can have at most {} URL buttons", MAX_URL_BUTTONS));
+    }
+    if count(|b| matches!(b, TemplateButton::PhoneNumber { .. })) > MAX_PHONE_NUMBER_BUTTONS {
+        return Err(format!("A template can have at most {} phone number button", MAX_PHONE_NUMBER_BUTTONS));
+    }
+    if count(|b| matches!(b, TemplateButton::CopyCode { .. })) > MAX_COPY_CODE_BUTTONS {
+        return Err(format!("A template can have at most {} copy code button", MAX_COPY_CODE_BUTTONS));
+    }
+
+    // Quick replies and call-to-action buttons have to be grouped, not interleaved.
+    let group_changes = buttons.windows(2)
+        .filter(|pair| pair[0].is_quick_reply() != pair[1].is_quick_reply())
+        .count();
+    if group_changes > 1 {
+        return Err("Quick reply buttons must be grouped together, before or after the other buttons".to_string());
+    }
+
+    for button in buttons {
+        match button {
+            TemplateButton::QuickReply { text } => check_text(text)?,
+            TemplateButton::Url { text, url, example } => {
+                check_text(text)?;
+                check_url(url, example)?;
+            }
+            TemplateButton::PhoneNumber { text, phone_number } => {
+                check_text(text)?;
+                let digits = phone_number.trim_start_matches('+');
+                if digits.is_empty()
+                    || !digits.chars().all(|c| c.is_ascii_digit())
+                    || phone_number.len() > MAX_PHONE_NUMBER_LENGTH
+                {
+                    return Err(format!(
+                        "Phone number '{}' must be digits with country code, up to {} characters",
+                        phone_number, MAX_PHONE_NUMBER_LENGTH
+                    ));
+                }
+            }
+            TemplateButton::CopyCode { example } => {
+                if example.is_empty() || example.chars().count() > MAX_COPY_CODE_LENGTH {
+                    return Err(format!(
+                        "Copy code example must have between 1 and {} characters",
+                        MAX_COPY_CODE_LENGTH
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
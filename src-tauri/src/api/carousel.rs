@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::buttons::{self, TemplateButton};
+use super::media::{self, MediaKind};
+use super::variables::{self, TextPart, VariableError};
+use crate::error::app_error::AppError;
+
+pub const MIN_CARDS: usize = 2;
+pub const MAX_CARDS: usize = 10;
+pub const MAX_CARD_BODY_LENGTH: usize = 160;
+pub const MAX_CARD_BUTTONS: usize = 2;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum CardHeaderType {
+    #[serde(rename = "IMAGE")]
+    Image,
+    #[serde(rename = "VIDEO")]
+    Video,
+}
+
+impl CardHeaderType {
+    pub fn media_kind(&self) -> MediaKind {
+        match self {
+            CardHeaderType::Image => MediaKind::Image,
+            CardHeaderType::Video => MediaKind::Video,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CardHeaderType::Image => "IMAGE",
+            CardHeaderType::Video => "VIDEO",
+        }
+    }
+}
+
+/// A carousel card as received from the form, with its media still to be uploaded.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CardParams {
+    pub header_type: CardHeaderType,
+    pub media_data: Vec<u8>,
+    pub media_name: Option<String>,
+    pub body: String,
+    pub examples: Option<Vec<String>>,
+    #[serde(default)]
+    pub buttons: Vec<TemplateButton>,
+}
+
+impl CardParams {
+    pub fn media_name(&self) -> String {
+        self.media_name.clone()
+            .unwrap_or_else(|| self.header_type.media_kind().default_file_name().to_string())
+    }
+}
+
+/// A carousel card whose media has been uploaded for one app_id.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CarouselCard {
+    pub header_type: CardHeaderType,
+    pub media_id: String,
+    pub media_url: String,
+    pub body: String,
+    pub sample_text: String,
+    pub buttons: Vec<TemplateButton>,
+}
+
+impl CarouselCard {
+    pub fn new(card: &CardParams, media_id: &str, media_url: &str) -> Result<Self, VariableError> {
+        let examples = card.examples.as_deref().unwrap_or_default();
+        let sample_text = variables::fill_examples(&card.body, TextPart::Body, examples)?;
+
+        Ok(CarouselCard {
+            header_type: card.header_type,
+            media_id: media_id.to_string(),
+            media_url: media_url.to_string(),
+            body: card.body.clone(),
+            sample_text,
+            buttons: card.buttons.clone(),
+        })
+    }
+
+    fn to_gupshup_value(&self) -> Value {
+        json!({
+            "headerType": self.header_type.as_str(),
+            "mediaId": self.media_id,
+            "mediaUrl": self.media_url,
+            "exampleMedia": self.media_url,
+            "body": self.body,
+            "sampleText": self.sample_text,
+            "buttons": buttons::to_gupshup_value(&self.buttons),
+        })
+    }
+}
+
+/// Serializes the cards into the JSON string Gupshup expects in the `cards` form field.
+pub fn to_form_value(cards: &[CarouselCard]) -> String {
+    Value::Array(cards.iter().map(CarouselCard::to_gupshup_value).collect()).to_string()
+}
+
+fn button_kind(button: &TemplateButton) -> &'static str {
+    match button {
+        TemplateButton::QuickReply { .. } => "QUICK_REPLY",
+        TemplateButton::Url { .. } => "URL",
+        TemplateButton::PhoneNumber { .. } => "PHONE_NUMBER",
+        TemplateButton::CopyCode { .. } => "COPY_CODE",
+    }
+}
+
+/// A validation error of the card with the given 1-based number.
+fn invalid_card(number: usize, message: impl std::fmt::Display) -> AppError {
+    AppError::validation(format!("Card {}: {}", number, message)).with_detail("card", number)
+}
+
+/// A variable error in the body of the card with the given 1-based number.
+pub fn card_variable_error(number: usize, e: VariableError) -> AppError {
+    AppError {
+        message: format!("Card {}: {}", number, e),
+        ..AppError::from(e)
+    }
+    .with_detail("card", number)
+}
+
+/// Checks the card count, that all cards share the same layout, and each card's media, body and buttons.
+pub fn validate_cards(cards: &[CardParams]) -> Result<(), AppError> {
+    if !(MIN_CARDS..=MAX_CARDS).contains(&cards.len()) {
+        return Err(AppError::validation(format!(
+            "A carousel needs between {} and {} cards, got {}",
+            MIN_CARDS, MAX_CARDS, cards.len()
+        )));
+    }
+
+    let first = &cards[0];
+    let first_buttons: Vec<&str> = first.buttons.iter().map(button_kind).collect();
+
+    for (index, card) in cards.iter().enumerate() {
+        let number = index + 1;
+        if card.header_type != first.header_type {
+            return Err(invalid_card(number, "must use the same header type as the first card"));
+        }
+
+        // Image cards are checked by the header image pipeline, which converts them to JPEG.
+        if card.header_type != CardHeaderType::Image {
+            media::validate_media(card.header_type.media_kind(), &card.media_data, &card.media_name())
+                .map_err(|e| invalid_card(number, e))?;
+        }
+
+        if card.body.trim().is_empty() {
+            return Err(invalid_card(number, "body cannot be empty"));
+        }
+        if card.body.chars().count() > MAX_CARD_BODY_LENGTH {
+            return Err(invalid_card(number, format!("body is longer than {} characters", MAX_CARD_BODY_LENGTH)));
+        }
+        variables::fill_examples(&card.body, TextPart::Body, card.examples.as_deref().unwrap_or_default())
+            .map_err(|e| card_variable_error(number, e))?;
+
+        if card.buttons.is_empty() || card.buttons.len() > MAX_CARD_BUTTONS {
+            return Err(invalid_card(number, format!("needs between 1 and {} buttons", MAX_CARD_BUTTONS)));
+        }
+        if card.buttons.iter().any(|button| matches!(button, TemplateButton::CopyCode { .. })) {
+            return Err(invalid_card(number, "cannot have a copy code button"));
+        }
+        buttons::validate_buttons(&card.buttons).map_err(|e| invalid_card(number, e))?;
+
+        let kinds: Vec<&str> = card.buttons.iter().map(button_kind).collect();
+        if kinds != first_buttons {
+            return Err(invalid_card(number, "must have the same buttons, in the same order, as the first card"));
+        }
+    }
+
+    Ok(())
+}
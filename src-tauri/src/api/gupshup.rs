@@ -1,7 +1,23 @@
-use reqwest::{Client};
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use super::authentication::{self, AuthenticationOptions};
+use super::buttons::{self, TemplateButton};
+use super::carousel::{self, CardParams, CarouselCard};
+use super::limited_offer::LimitedTimeOffer;
+use super::media::{self, MediaKind};
+use super::media_cache::MediaCache;
+use super::retry::{self, RetryPolicy};
+use super::variables::{self, TextPart, VariableError};
+use crate::error::app_error::{AppError, ErrorCode, FailureAction};
+
+pub const DEFAULT_LANGUAGE_CODE: &str = "pt_BR";
+pub const MAX_FOOTER_LENGTH: usize = 60;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum TemplateCategory {
@@ -9,6 +25,31 @@ pub enum TemplateCategory {
     Marketing,
     #[serde(rename = "UTILITY")]
     Utility,
+    #[serde(rename = "AUTHENTICATION")]
+    Authentication,
+}
+
+impl TemplateCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateCategory::Marketing => "MARKETING",
+            TemplateCategory::Utility => "UTILITY",
+            TemplateCategory::Authentication => "AUTHENTICATION",
+        }
+    }
+}
+
+impl FromStr for TemplateCategory {
+    type Err = String;
+
+    fn from_str(category: &str) -> Result<Self, Self::Err> {
+        match category {
+            "MARKETING" => Ok(TemplateCategory::Marketing),
+            "UTILITY" => Ok(TemplateCategory::Utility),
+            "AUTHENTICATION" => Ok(TemplateCategory::Authentication),
+            _ => Err("Invalid category. Must be 'MARKETING', 'UTILITY' or 'AUTHENTICATION'".to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -17,6 +58,43 @@ pub enum TemplateType {
     Text,
     #[serde(rename = "IMAGE")]
     Image,
+    #[serde(rename = "VIDEO")]
+    Video,
+    #[serde(rename = "DOCUMENT")]
+    Document,
+    #[serde(rename = "LOCATION")]
+    Location,
+    #[serde(rename = "CAROUSEL")]
+    Carousel,
+}
+
+impl TemplateType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TemplateType::Text => "TEXT",
+            TemplateType::Image => "IMAGE",
+            TemplateType::Video => "VIDEO",
+            TemplateType::Document => "DOCUMENT",
+            TemplateType::Location => "LOCATION",
+            TemplateType::Carousel => "CAROUSEL",
+        }
+    }
+}
+
+impl FromStr for TemplateType {
+    type Err = String;
+
+    fn from_str(template_type: &str) -> Result<Self, Self::Err> {
+        match template_type {
+            "TEXT" => Ok(TemplateType::Text),
+            "IMAGE" => Ok(TemplateType::Image),
+            "VIDEO" => Ok(TemplateType::Video),
+            "DOCUMENT" => Ok(TemplateType::Document),
+            "LOCATION" => Ok(TemplateType::Location),
+            "CAROUSEL" => Ok(TemplateType::Carousel),
+            _ => Err("Invalid template type. Must be 'TEXT', 'IMAGE', 'VIDEO', 'DOCUMENT', 'LOCATION' or 'CAROUSEL'".to_string()),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,9 +107,15 @@ pub struct TemplateRequest {
     pub vertical: String,
     pub template_type: TemplateType,
     pub example: String,
+    pub header: Option<String>,
     pub example_header: Option<String>,
     pub media_id: Option<String>,
     pub media_url: Option<String>,
+    pub buttons: Vec<TemplateButton>,
+    pub footer: Option<String>,
+    pub authentication: Option<AuthenticationOptions>,
+    pub cards: Vec<CarouselCard>,
+    pub limited_time_offer: Option<LimitedTimeOffer>,
 }
 
 impl TemplateRequest {
@@ -45,16 +129,22 @@ impl TemplateRequest {
     ) -> Self {
         TemplateRequest {
             element_name: name.to_string(),
-            language_code: "pt_BR".to_string(),
+            language_code: DEFAULT_LANGUAGE_CODE.to_string(),
             content: content.to_string(),
             category,
             app_id: app_id.to_string(),
             vertical: vertical.to_string(),
             template_type,
             example: content.to_string(),
+            header: None,
             example_header: None,
             media_id: None,
             media_url: None,
+            buttons: Vec::new(),
+            footer: None,
+            authentication: None,
+            cards: Vec::new(),
+            limited_time_offer: None,
         }
     }
 
@@ -64,10 +154,135 @@ impl TemplateRequest {
         self
     }
 
-    pub fn with_header_example(mut self, example_header: &str) -> Self {
-        self.example_header = Some(example_header.to_string());
+    pub fn with_language_code(mut self, language_code: &str) -> Self {
+        self.language_code = language_code.to_string();
         self
     }
+
+    /// Fills the body variables with one example value each to build the `example` text.
+    pub fn with_body_examples(mut self, examples: &[String]) -> Result<Self, VariableError> {
+        self.example = variables::fill_examples(&self.content, TextPart::Body, examples)?;
+        Ok(self)
+    }
+
+    pub fn with_header(mut self, header: &str, examples: &[String]) -> Result<Self, VariableError> {
+        self.example_header = Some(variables::fill_examples(header, TextPart::Header, examples)?);
+        self.header = Some(header.to_string());
+        Ok(self)
+    }
+
+    pub fn with_buttons(mut self, buttons: Vec<TemplateButton>) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    pub fn with_footer(mut self, footer: &str) -> Self {
+        self.footer = Some(footer.to_string());
+        self
+    }
+
+    pub fn with_cards(mut self, cards: Vec<CarouselCard>) -> Self {
+        self.cards = cards;
+        self
+    }
+
+    pub fn with_limited_time_offer(mut self, offer: LimitedTimeOffer) -> Self {
+        self.limited_time_offer = Some(offer);
+        self
+    }
+
+    pub fn with_authentication(mut self, authentication: AuthenticationOptions) -> Self {
+        self.example = authentication::example_for(&self.content);
+        self.authentication = Some(authentication);
+        self
+    }
+
+    /// The form fields Gupshup expects when creating this template.
+    pub fn form_fields(&self) -> HashMap<&'static str, String> {
+        let mut form = HashMap::new();
+        form.insert("elementName", self.element_name.clone());
+        form.insert("languageCode", self.language_code.clone());
+        form.insert("content", self.content.clone());
+        form.insert("category", self.category.as_str().to_string());
+        form.insert("appId", self.app_id.clone());
+        form.insert("vertical", self.vertical.clone());
+        form.insert("templateType", self.template_type.as_str().to_string());
+        form.insert("example", self.example.clone());
+        form.insert("enableSample", "true".to_string());
+        form.insert("allowTemplateCategoryChange", "true".to_string());
+        form.insert("checkerApprovalRequired", "false".to_string());
+        
+        if let Some(ref header) = self.header {
+            form.insert("header", header.clone());
+        }
+
+        if let Some(ref example_header) = self.example_header {
+            form.insert("exampleHeader", example_header.clone());
+        } else {
+            form.insert("exampleHeader", "".to_string());
+        }
+
+        if let Some(ref media_id) = self.media_id {
+            form.insert("mediaId", media_id.clone());
+        }
+
+        if let Some(ref media_url) = self.media_url {
+            form.insert("mediaUrl", media_url.clone());
+        }
+
+        if let Some(ref footer) = self.footer {
+            form.insert("footer", footer.clone());
+        }
+
+        if let Some(ref authentication) = self.authentication {
+            form.insert("addSecurityRecommendation", authentication.add_security_recommendation.to_string());
+            if let Some(minutes) = authentication.code_expiration_minutes {
+                form.insert("codeExpirationMinutes", minutes.to_string());
+            }
+            form.insert("buttons", authentication.buttons_form_value());
+        } else if !self.buttons.is_empty() {
+            form.insert("buttons", buttons::to_form_value(&self.buttons));
+        }
+
+        if !self.cards.is_empty() {
+            form.insert("cards", carousel::to_form_value(&self.cards));
+        }
+
+        if let Some(ref offer) = self.limited_time_offer {
+            form.insert("isLTO", "true".to_string());
+            form.insert("limitedOfferText", offer.text.clone());
+            form.insert("hasExpiration", offer.has_expiration.to_string());
+            if let Some(expiration) = offer.expiration_example {
+                form.insert("expirationTimeExample", expiration.to_string());
+            }
+        }
+
+        form
+    }
+}
+
+/// Checks a WhatsApp language code such as `en`, `pt_BR` or `es_AR`.
+pub fn validate_language_code(language_code: &str) -> Result<(), String> {
+    let (language, region) = match language_code.split_once('_') {
+        Some((language, region)) => (language, Some(region)),
+        None => (language_code, None),
+    };
+    let language_ok = (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+    let region_ok = region.is_none_or(|region| region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()));
+    if !language_ok || !region_ok {
+        return Err(format!("Invalid language code '{}'. Expected a code such as 'en', 'pt_BR' or 'es_AR'", language_code));
+    }
+    Ok(())
+}
+
+pub fn validate_footer(footer: &str) -> Result<(), String> {
+    if footer.chars().count() > MAX_FOOTER_LENGTH {
+        return Err(format!("Footer is longer than {} characters", MAX_FOOTER_LENGTH));
+    }
+    if footer.contains("{{") {
+        return Err("Footer cannot contain variables".to_string());
+    }
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -76,13 +291,51 @@ pub struct GupshupResponse {
     pub message: Option<String>,
 }
 
+impl GupshupResponse {
+    /// Succeeds only when both the HTTP status and the body report success, otherwise
+    /// classifies the failure from the full body.
+    pub fn check(app_id: &str, status: StatusCode, body: &str) -> Result<(), AppError> {
+        match serde_json::from_str::<GupshupResponse>(body) {
+            Ok(response) if status.is_success() && response.status == "success" => Ok(()),
+            _ => Err(AppError::gupshup(app_id, status, body)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemplateRecord {
+    pub id: String,
+    #[serde(rename = "elementName")]
+    pub element_name: String,
+    pub category: String,
+    #[serde(rename = "languageCode")]
+    pub language_code: String,
+    pub status: String,
+    #[serde(rename = "templateType")]
+    pub template_type: String,
+    #[serde(rename = "data", default)]
+    pub content: String,
+    #[serde(rename = "createdOn", default)]
+    pub created_on: Option<i64>,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateListResponse {
+    pub status: String,
+    pub message: Option<String>,
+    #[serde(default)]
+    pub templates: Vec<TemplateRecord>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MediaResponse {
     pub status: String,
     pub media: Option<MediaDetails>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MediaDetails {
     #[serde(rename = "fileName")]
     pub file_name: String,
@@ -95,6 +348,15 @@ pub struct GupshupClient {
     base_url: String,
     api_key: String,
     session_cookie: String,
+    retry: RetryPolicy,
+    media_cache: Option<Arc<MediaCache>>,
+}
+
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(retry::parse_retry_after)
 }
 
 impl GupshupClient {
@@ -105,151 +367,288 @@ impl GupshupClient {
             base_url: "https://api.gupshup.io/wa/app".to_string(),
             api_key: api_key.to_string(),
             session_cookie: session_cookie.to_string(),
+            retry: RetryPolicy::default(),
+            media_cache: None,
         }
     }
 
-    pub async fn upload_media(&self, app_id: &str, file_name: &str, file_data: Vec<u8>) -> Result<MediaResponse, String> {
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_media_cache(mut self, media_cache: Arc<MediaCache>) -> Self {
+        self.media_cache = Some(media_cache);
+        self
+    }
+
+    /// Forgets the cached media handles of the app_id so the next template uploads again.
+    pub async fn forget_media(&self, app_id: &str) {
+        if let Some(ref cache) = self.media_cache {
+            cache.forget_app(app_id).await;
+        }
+    }
+
+    /// Sends a request until it succeeds, fails with an error that is not transient or
+    /// runs out of attempts. Returns the result with the number of attempts made.
+    async fn send_with_retry<T, F, Fut>(&self, app_id: &str, send: F) -> Result<(T, u32), AppError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match send().await {
+                Ok(value) => return Ok((value, attempt)),
+                Err(e) if attempt < self.retry.max_attempts && e.code.action() == FailureAction::Retry => {
+                    let delay = self.retry.delay(attempt, e.retry_after);
+                    println!("Transient error for app_id {}: {}. Retrying in {:?} (attempt {}/{})",
+                        app_id, e.message, delay, attempt + 1, self.retry.max_attempts);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(AppError { attempts: Some(attempt), ..e }),
+            }
+        }
+    }
+
+    pub async fn upload_media(&self, app_id: &str, file_name: &str, file_data: Vec<u8>, kind: MediaKind) -> Result<MediaResponse, AppError> {
         println!("Uploading media '{}' for app_id: {}, size: {} bytes", file_name, app_id, file_data.len());
         let url = format!("https://api.gupshup.io/wa/{}/wa/media/v2", app_id);
+
+        let mime = media::validate_media(kind, &file_data, file_name)
+            .map_err(|e| AppError::new(ErrorCode::MediaRejected, e).with_app_id(app_id))?;
+        println!("Detected MIME type {} for '{}'", mime, file_name);
+        
+        let (media_response, _) = self.send_with_retry(app_id, || {
+            self.send_media(app_id, &url, file_name, &file_data, mime)
+        }).await?;
         
-        let part = reqwest::multipart::Part::bytes(file_data)
+        if media_response.status == "success" {
+            if let Some(ref media) = media_response.media {
+                println!("Media uploaded successfully. ID: {}, URL: {}", media.id, media.url);
+            } else {
+                println!("Media upload successful but no media details returned");
+            }
+        } else {
+            println!("Media upload failed with status: {}", media_response.status);
+        }
+        
+        Ok(media_response)
+    }
+
+    async fn send_media(&self, app_id: &str, url: &str, file_name: &str, file_data: &[u8], mime: &str) -> Result<MediaResponse, AppError> {
+        let part = reqwest::multipart::Part::bytes(file_data.to_vec())
             .file_name(file_name.to_string())
-            .mime_str("image/jpeg")
-            .map_err(|e| format!("MIME error: {}", e))?;
+            .mime_str(mime)
+            .map_err(|e| AppError::new(ErrorCode::MediaRejected, format!("MIME error: {}", e)).with_app_id(app_id))?;
         
         let form = reqwest::multipart::Form::new().part("file", part);
 
         println!("Sending media upload request to: {}", url);
         let response = self.client
-            .post(&url)
+            .post(url)
             .header("Cookie", format!("session={}", self.session_cookie))
             .multipart(form)
             .send()
             .await
-            .map_err(|e| format!("Request error: {}", e))?;
+            .map_err(|e| AppError::from(e).with_app_id(app_id))?;
         
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = retry_after(&response);
             let error_text = response.text().await
-                .map_err(|e| format!("Error reading response: {}", e))?;
+                .map_err(|e| AppError::from(e).with_app_id(app_id))?;
             
             println!("Error uploading media: HTTP {}: {}", status, error_text);
-            return Err(format!("HTTP error {}: {}", status, error_text));
+            let error = AppError::gupshup(app_id, status, &error_text).with_retry_after(retry_after);
+            return Err(match error.code {
+                ErrorCode::Unauthorized | ErrorCode::RateLimited | ErrorCode::ServerError => error,
+                _ => AppError { code: ErrorCode::MediaRejected, ..error },
+            });
         }
 
         println!("Media upload response received, parsing...");
-        let media_response = response.json::<MediaResponse>().await
-            .map_err(|e| format!("Error parsing response: {}", e))?;
-        
-        if media_response.status == "success" {
-            if let Some(ref media) = media_response.media {
-                println!("Media uploaded successfully. ID: {}, URL: {}", media.id, media.url);
-            } else {
-                println!("Media upload successful but no media details returned");
-            }
-        } else {
-            println!("Media upload failed with status: {}", media_response.status);
+        response.json::<MediaResponse>().await
+            .map_err(|e| AppError::from(e).with_app_id(app_id))
+    }
+
+    /// Returns the cached handle for these bytes on the app_id, uploading them first if needed.
+    async fn upload_or_reuse(&self, app_id: &str, file_name: &str, data: &[u8], kind: MediaKind) -> Result<MediaDetails, AppError> {
+        if let Some(media) = self.media_cache.as_ref().and_then(|cache| cache.get(app_id, data)) {
+            println!("Reusing media '{}' already uploaded to app_id {}: {}", file_name, app_id, media.id);
+            return Ok(media);
+        }
+
+        let media_response = self.upload_media(app_id, file_name, data.to_vec(), kind).await?;
+        let media = match media_response.media {
+            Some(media) if media_response.status == "success" => media,
+            _ => return Err(AppError::new(
+                ErrorCode::MediaRejected,
+                format!("Failed to upload media '{}'", file_name),
+            ).with_app_id(app_id)),
+        };
+
+        if let Some(ref cache) = self.media_cache {
+            cache.insert(app_id, data, &media).await;
         }
+        Ok(media)
+    }
+
+    /// Uploads the header media for a template of the given type and returns its handle.
+    pub async fn upload_header_media(
+        &self,
+        app_id: &str,
+        template_type: &TemplateType,
+        media_data: &[u8],
+        media_name: Option<String>
+    ) -> Result<MediaDetails, AppError> {
+        let kind = MediaKind::for_template_type(template_type)
+            .ok_or_else(|| AppError::validation(format!("Template type {:?} does not take a media header", template_type)))?;
+        let file_name = media_name.unwrap_or_else(|| kind.default_file_name().to_string());
+        println!("Uploading {:?} '{}' ({} bytes) for template", kind, file_name, media_data.len());
         
-        Ok(media_response)
+        self.upload_or_reuse(app_id, &file_name, media_data, kind).await
     }
 
-    pub async fn create_template_with_image(
-        &self, 
-        app_id: &str, 
-        mut template: TemplateRequest, 
-        image_data: Option<Vec<u8>>,
-        image_name: Option<String>
-    ) -> Result<GupshupResponse, String> {
-        println!("Creating template with image for app_id: {}, template: {}", 
-            app_id, template.element_name);
-            
-        if let Some(data) = image_data {
-            let file_name = image_name.unwrap_or_else(|| "image.jpg".to_string());
-            println!("Uploading image '{}' ({} bytes) for template", file_name, data.len());
-            
-            let media_response = self.upload_media(app_id, &file_name, data).await?;
-            
-            if media_response.status != "success" {
-                let error_msg = media_response.media
-                    .map_or("Unknown error".to_string(), 
-                    |m| format!("Error with file {}", m.file_name));
-                
-                return Err(format!("Failed to upload media: {}", error_msg));
-            }
+    /// Uploads the media of every carousel card for the app_id, in card order.
+    pub async fn upload_carousel_cards(&self, app_id: &str, cards: &[CardParams]) -> Result<Vec<CarouselCard>, AppError> {
+        let mut uploaded = Vec::with_capacity(cards.len());
+        
+        for (index, card) in cards.iter().enumerate() {
+            println!("Uploading media for carousel card {}/{} for app_id: {}", index + 1, cards.len(), app_id);
+            let kind = card.header_type.media_kind();
+            let media_details = self.upload_or_reuse(app_id, &card.media_name(), &card.media_data, kind).await?;
             
-            if let Some(media_details) = media_response.media {
-                template = template.with_media(&media_details.id, &media_details.url);
-            }
+            let card = CarouselCard::new(card, &media_details.id, &media_details.url)
+                .map_err(|e| carousel::card_variable_error(index + 1, e).with_app_id(app_id))?;
+            uploaded.push(card);
         }
         
-        self.create_template(app_id, template).await
+        Ok(uploaded)
     }
 
-    pub async fn create_template(&self, app_id: &str, template: TemplateRequest) -> Result<GupshupResponse, String> {
+    /// Creates the template and returns how many attempts it took.
+    pub async fn create_template(&self, app_id: &str, template: TemplateRequest) -> Result<u32, AppError> {
         let url = format!("{}/{}/template", self.base_url, app_id);
         println!("Creating template '{}' for app_id: {} at URL: {}", 
             template.element_name, app_id, url);
         
         println!("Preparing form data for template creation");
-        let mut form = HashMap::new();
-        form.insert("elementName", template.element_name);
-        form.insert("languageCode", template.language_code);
-        form.insert("content", template.content.clone());
-        form.insert("category", match template.category {
-            TemplateCategory::Marketing => "MARKETING".to_string(),
-            TemplateCategory::Utility => "UTILITY".to_string(),
-        });
-        form.insert("appId", template.app_id);
-        form.insert("vertical", template.vertical);
-        form.insert("templateType", match template.template_type {
-            TemplateType::Text => "TEXT".to_string(),
-            TemplateType::Image => "IMAGE".to_string(),
-        });
-        form.insert("example", template.example);
-        form.insert("enableSample", "true".to_string());
-        form.insert("allowTemplateCategoryChange", "true".to_string());
-        form.insert("checkerApprovalRequired", "false".to_string());
-        
-        if let Some(example_header) = template.example_header {
-            form.insert("exampleHeader", example_header);
-        } else {
-            form.insert("exampleHeader", "".to_string());
-        }
+        let form = template.form_fields();
 
-        if let Some(media_id) = template.media_id {
-            form.insert("mediaId", media_id);
-        }
-
-        if let Some(media_url) = template.media_url {
-            form.insert("mediaUrl", media_url);
+        println!("Sending template creation request with content: '{}'", template.content);
+        match self.send_with_retry(app_id, || self.send_template_form(app_id, &url, &form)).await {
+            Ok(((), attempts)) => Ok(attempts),
+            // An earlier attempt reached Gupshup and created the template before its response was lost.
+            Err(AppError { code: ErrorCode::TemplateAlreadyExists, attempts: Some(attempts), .. }) if attempts > 1 => {
+                println!("Template '{}' already exists for app_id {} after a retry, counting it as created",
+                    template.element_name, app_id);
+                Ok(attempts)
+            }
+            Err(e) => Err(e),
         }
+    }
 
-        println!("Sending template creation request with content: '{}'", template.content);
+    async fn send_template_form(&self, app_id: &str, url: &str, form: &HashMap<&str, String>) -> Result<(), AppError> {
         let response = self.client
-            .post(&url)
+            .post(url)
             .header("Content-Type", "application/x-www-form-urlencoded")
             .header("Cookie", format!("session={}", self.session_cookie))
-            .form(&form)
+            .form(form)
             .send()
             .await
             .map_err(|e| {
                 println!("Error sending template creation request: {}", e);
-                format!("Request error: {}", e)
+                AppError::from(e).with_app_id(app_id)
             })?;
 
         let status = response.status();
+        let retry_after = retry_after(&response);
         println!("Received response with status: {}", status);
         
-        let gupshup_response = response.json::<GupshupResponse>().await
+        let body = response.text().await
             .map_err(|e| {
-                println!("Error parsing response: {}", e);
-                format!("Error parsing response: {}", e)
+                println!("Error reading response: {}", e);
+                AppError::from(e).with_app_id(app_id)
             })?;
         
-        println!("Template creation response: status={}, message={:?}", 
-            gupshup_response.status, gupshup_response.message);
+        println!("Template creation response: {}", body);
         
-        Ok(gupshup_response)
+        GupshupResponse::check(app_id, status, &body)
+            .map_err(|e| e.with_retry_after(retry_after))
+    }
+
+    pub async fn list_templates(&self, app_id: &str) -> Result<Vec<TemplateRecord>, AppError> {
+        let url = format!("{}/{}/template", self.base_url, app_id);
+        println!("Listing templates for app_id: {} at URL: {}", app_id, url);
+
+        let (templates, _) = self.send_with_retry(app_id, || self.send_list(app_id, &url)).await?;
+        Ok(templates)
+    }
+
+    async fn send_list(&self, app_id: &str, url: &str) -> Result<Vec<TemplateRecord>, AppError> {
+        let response = self.client
+            .get(url)
+            .header("apikey", &self.api_key)
+            .header("Cookie", format!("session={}", self.session_cookie))
+            .send()
+            .await
+            .map_err(|e| {
+                println!("Error sending template list request: {}", e);
+                AppError::from(e).with_app_id(app_id)
+            })?;
+
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        let body = response.text().await
+            .map_err(|e| AppError::from(e).with_app_id(app_id))?;
+
+        let list_response = match serde_json::from_str::<TemplateListResponse>(&body) {
+            Ok(list_response) if status.is_success() && list_response.status == "success" => list_response,
+            _ => {
+                println!("Error listing templates: HTTP {}: {}", status, body);
+                return Err(AppError::gupshup(app_id, status, &body).with_retry_after(retry_after));
+            }
+        };
+
+        println!("Found {} templates for app_id: {}", list_response.templates.len(), app_id);
+        Ok(list_response.templates)
+    }
+
+    pub async fn delete_template(&self, app_id: &str, element_name: &str) -> Result<(), AppError> {
+        let url = format!("{}/{}/template/{}", self.base_url, app_id, element_name);
+        println!("Deleting template '{}' for app_id: {} at URL: {}", element_name, app_id, url);
+
+        self.send_with_retry(app_id, || self.send_delete(app_id, &url)).await?;
+        Ok(())
+    }
+
+    async fn send_delete(&self, app_id: &str, url: &str) -> Result<(), AppError> {
+        let response = self.client
+            .delete(url)
+            .header("apikey", &self.api_key)
+            .header("Cookie", format!("session={}", self.session_cookie))
+            .send()
+            .await
+            .map_err(|e| {
+                println!("Error sending template deletion request: {}", e);
+                AppError::from(e).with_app_id(app_id)
+            })?;
+
+        let status = response.status();
+        let retry_after = retry_after(&response);
+        println!("Received response with status: {}", status);
+
+        let body = response.text().await
+            .map_err(|e| {
+                println!("Error reading response: {}", e);
+                AppError::from(e).with_app_id(app_id)
+            })?;
+
+        println!("Template deletion response: {}", body);
+
+        GupshupResponse::check(app_id, status, &body)
+            .map_err(|e| e.with_retry_after(retry_after))
     }
 }
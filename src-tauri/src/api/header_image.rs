@@ -0,0 +1,319 @@
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Rgb, RgbImage};
+use serde::Serialize;
+use std::io::Cursor;
+use std::path::Path;
+
+use super::media::MediaKind;
+
+/// Longest side a header image is downscaled to.
+pub const MAX_DIMENSION: u32 = 1600;
+const MIN_DIMENSION: u32 = 100;
+const MAX_ASPECT_RATIO: f32 = 4.0;
+/// Tried in order until the encoded JPEG fits the WhatsApp size limit.
+const JPEG_QUALITIES: [u8; 4] = [90, 80, 70, 60];
+
+/// What the pipeline found and changed for one image.
+#[derive(Debug, Serialize, Clone)]
+pub struct ImageReport {
+    pub file_name: String,
+    pub original_format: String,
+    pub original_width: u32,
+    pub original_height: u32,
+    pub original_size: usize,
+    pub width: u32,
+    pub height: u32,
+    pub size: usize,
+    /// Empty when the image was already a compliant JPEG and is uploaded untouched.
+    pub changes: Vec<String>,
+}
+
+pub struct ProcessedImage {
+    pub data: Vec<u8>,
+    pub file_name: String,
+    pub report: ImageReport,
+}
+
+fn has_exif(data: &[u8]) -> bool {
+    data.windows(6).any(|window| window == b"Exif\0\0")
+}
+
+/// Blends transparent pixels onto white, since JPEG has no alpha channel.
+fn flatten_onto_white(image: &DynamicImage) -> RgbImage {
+    let rgba = image.to_rgba8();
+    let mut rgb = RgbImage::new(rgba.width(), rgba.height());
+    for (x, y, pixel) in rgba.enumerate_pixels() {
+        let alpha = pixel[3] as u16;
+        let blend = |channel: u8| ((channel as u16 * alpha + 255 * (255 - alpha)) / 255) as u8;
+        rgb.put_pixel(x, y, Rgb([blend(pixel[0]), blend(pixel[1]), blend(pixel[2])]));
+    }
+    rgb
+}
+
+fn encode_jpeg(image: &RgbImage, quality: u8) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    JpegEncoder::new_with_quality(&mut data, quality)
+        .encode_image(image)
+        .map_err(|e| format!("Failed to encode JPEG: {}", e))?;
+    Ok(data)
+}
+
+fn header_image_format(data: &[u8], file_name: &str) -> Result<ImageFormat, String> {
+    let format = image::guess_format(data)
+        .map_err(|_| format!("'{}' is not a recognised image", file_name))?;
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::WebP) {
+        return Err(format!("'{}' is a {:?} image, only JPEG, PNG and WebP can be used as a header", file_name, format));
+    }
+    Ok(format)
+}
+
+fn check_dimensions(file_name: &str, width: u32, height: u32) -> Result<(), String> {
+    if width.min(height) < MIN_DIMENSION {
+        return Err(format!(
+            "'{}' is {}x{} pixels, header images must be at least {} pixels on each side",
+            file_name, width, height, MIN_DIMENSION
+        ));
+    }
+    let aspect_ratio = width.max(height) as f32 / width.min(height) as f32;
+    if aspect_ratio > MAX_ASPECT_RATIO {
+        return Err(format!(
+            "'{}' is {}x{} pixels, an aspect ratio above {}:1 is cropped beyond recognition by WhatsApp",
+            file_name, width, height, MAX_ASPECT_RATIO
+        ));
+    }
+    Ok(())
+}
+
+/// Runs the format and dimension checks of `process_header_image` on the image header
+/// alone, without decoding the pixels, so a template can be validated while it is edited.
+pub fn inspect_header_image(data: &[u8], file_name: &str) -> Result<(), String> {
+    let format = header_image_format(data, file_name)?;
+    let decoder = ImageReader::with_format(Cursor::new(data), format)
+        .into_decoder()
+        .map_err(|e| format!("Failed to decode '{}': {}", file_name, e))?;
+    let (width, height) = decoder.dimensions();
+    check_dimensions(file_name, width, height)
+}
+
+/// Encodes at the first of `JPEG_QUALITIES` that fits `max_size`, returning the JPEG and
+/// that quality, or `None` when even the lowest quality is too large.
+fn encode_jpeg_to_fit(image: &RgbImage, max_size: usize) -> Result<Option<(Vec<u8>, u8)>, String> {
+    for quality in JPEG_QUALITIES {
+        let jpeg = encode_jpeg(image, quality)?;
+        if jpeg.len() <= max_size {
+            return Ok(Some((jpeg, quality)));
+        }
+    }
+    Ok(None)
+}
+
+/// Prepares a header image for WhatsApp: checks format, dimensions and aspect ratio,
+/// then converts it to a JPEG without EXIF metadata that fits `MAX_DIMENSION` and the
+/// image size limit. A JPEG that already complies is returned unchanged.
+pub fn process_header_image(data: &[u8], file_name: &str) -> Result<ProcessedImage, String> {
+    let format = header_image_format(data, file_name)?;
+    let mut decoder = ImageReader::with_format(Cursor::new(data), format)
+        .into_decoder()
+        .map_err(|e| format!("Failed to decode '{}': {}", file_name, e))?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| format!("Failed to decode '{}': {}", file_name, e))?;
+    let (original_width, original_height) = (image.width(), image.height());
+    check_dimensions(file_name, original_width, original_height)?;
+
+    let max_size = MediaKind::Image.max_size();
+    let exif = format == ImageFormat::Jpeg && has_exif(data);
+    let report = |file_name: &str, width: u32, height: u32, size: usize, changes: Vec<String>| ImageReport {
+        file_name: file_name.to_string(),
+        original_format: format!("{:?}", format).to_uppercase(),
+        original_width,
+        original_height,
+        original_size: data.len(),
+        width,
+        height,
+        size,
+        changes,
+    };
+
+    if format == ImageFormat::Jpeg && !exif && original_width.max(original_height) <= MAX_DIMENSION && data.len() <= max_size {
+        return Ok(ProcessedImage {
+            data: data.to_vec(),
+            file_name: file_name.to_string(),
+            report: report(file_name, original_width, original_height, data.len(), Vec::new()),
+        });
+    }
+
+    let mut changes = Vec::new();
+    if format != ImageFormat::Jpeg {
+        changes.push(format!("Converted {:?} to JPEG", format));
+    }
+    if orientation != Orientation::NoTransforms {
+        image.apply_orientation(orientation);
+        changes.push("Applied the EXIF orientation to the pixels".to_string());
+    }
+    if exif {
+        changes.push("Removed EXIF metadata".to_string());
+    }
+    if image.width().max(image.height()) > MAX_DIMENSION {
+        let (width, height) = (image.width(), image.height());
+        image = image.resize(MAX_DIMENSION, MAX_DIMENSION, FilterType::Lanczos3);
+        changes.push(format!("Downscaled from {}x{} to {}x{}", width, height, image.width(), image.height()));
+    }
+    // PNGs often carry an alpha channel without a single transparent pixel.
+    let transparent = image.color().has_alpha() && image.to_rgba8().pixels().any(|pixel| pixel[3] < u8::MAX);
+    let rgb = if transparent {
+        changes.push("Flattened transparency onto a white background".to_string());
+        flatten_onto_white(&image)
+    } else {
+        image.to_rgb8()
+    };
+
+    let (encoded, quality) = encode_jpeg_to_fit(&rgb, max_size)?.ok_or_else(|| format!(
+        "'{}' is still above {} bytes after converting it to JPEG at quality {}",
+        file_name, max_size, JPEG_QUALITIES[JPEG_QUALITIES.len() - 1]
+    ))?;
+    if quality != JPEG_QUALITIES[0] {
+        changes.push(format!("Lowered JPEG quality to {} to fit {} bytes", quality, max_size));
+    }
+
+    let processed_name = Path::new(file_name).with_extension("jpg").to_string_lossy().into_owned();
+    let report = report(&processed_name, rgb.width(), rgb.height(), encoded.len(), changes);
+    Ok(ProcessedImage {
+        data: encoded,
+        file_name: processed_name,
+        report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn jpeg(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, y| Rgb([(x % 256) as u8, (y % 256) as u8, 128]));
+        encode_jpeg(&image, 90).unwrap()
+    }
+
+    fn png(image: RgbaImage) -> Vec<u8> {
+        let mut data = Vec::new();
+        DynamicImage::ImageRgba8(image).write_to(&mut Cursor::new(&mut data), ImageFormat::Png).unwrap();
+        data
+    }
+
+    /// Inserts an APP1 segment with an EXIF orientation tag right after the JPEG start marker.
+    fn with_exif(jpeg: Vec<u8>, orientation: u16) -> Vec<u8> {
+        let mut payload = b"Exif\0\0II\x2a\0\x08\0\0\0\x01\0\x12\x01\x03\0\x01\0\0\0".to_vec();
+        payload.extend_from_slice(&orientation.to_le_bytes());
+        payload.extend_from_slice(&[0; 6]);
+        let mut data = jpeg[..2].to_vec();
+        data.extend_from_slice(&[0xFF, 0xE1]);
+        data.extend_from_slice(&(payload.len() as u16 + 2).to_be_bytes());
+        data.extend_from_slice(&payload);
+        data.extend_from_slice(&jpeg[2..]);
+        data
+    }
+
+    #[test]
+    fn process_header_image_cases() {
+        let compliant = jpeg(400, 300);
+        let transparent = png(RgbaImage::from_pixel(200, 150, Rgba([0, 0, 0, 0])));
+        let opaque = png(RgbaImage::from_pixel(200, 150, Rgba([10, 20, 30, 255])));
+        let cases = [
+            ("compliant.jpg", compliant.clone(), Ok((400, 300, vec![]))),
+            ("exif.jpg", with_exif(compliant.clone(), 1), Ok((400, 300, vec!["Removed EXIF metadata"]))),
+            ("rotated.jpg", with_exif(compliant.clone(), 6), Ok((300, 400, vec![
+                "Applied the EXIF orientation to the pixels",
+                "Removed EXIF metadata",
+            ]))),
+            ("large.jpg", jpeg(2000, 1000), Ok((1600, 800, vec!["Downscaled from 2000x1000 to 1600x800"]))),
+            ("transparent.png", transparent, Ok((200, 150, vec![
+                "Converted Png to JPEG",
+                "Flattened transparency onto a white background",
+            ]))),
+            ("opaque.png", opaque, Ok((200, 150, vec!["Converted Png to JPEG"]))),
+            ("small.jpg", jpeg(80, 300), Err("at least 100 pixels")),
+            ("banner.jpg", jpeg(1000, 200), Err("aspect ratio above 4:1")),
+            ("animation.gif", b"GIF89a\x01\0\x01\0".to_vec(), Err("only JPEG, PNG and WebP")),
+            ("notes.jpg", b"not an image".to_vec(), Err("not a recognised image")),
+        ];
+
+        for (file_name, data, expected) in cases {
+            match (process_header_image(&data, file_name), expected) {
+                (Ok(processed), Ok((width, height, changes))) => {
+                    assert_eq!((processed.report.width, processed.report.height), (width, height), "{}", file_name);
+                    assert_eq!(processed.report.changes, changes, "{}", file_name);
+                    assert_eq!(processed.report.size, processed.data.len(), "{}", file_name);
+                    if changes.is_empty() {
+                        assert_eq!(processed.data, data, "{}", file_name);
+                        assert_eq!(processed.file_name, file_name);
+                    } else {
+                        assert_eq!(image::guess_format(&processed.data).unwrap(), ImageFormat::Jpeg, "{}", file_name);
+                        assert!(!has_exif(&processed.data), "{}", file_name);
+                        assert!(processed.file_name.ends_with(".jpg"), "{}", processed.file_name);
+                    }
+                }
+                (Err(error), Err(expected)) => assert!(error.contains(expected), "{}: {}", file_name, error),
+                (result, expected) => panic!("{}: got {:?}, expected {:?}", file_name, result.map(|p| p.report), expected),
+            }
+        }
+    }
+
+    #[test]
+    fn inspect_header_image_cases() {
+        let cases = [
+            (jpeg(400, 300), None),
+            (jpeg(2000, 1000), None),
+            (jpeg(80, 300), Some("at least 100 pixels")),
+            (jpeg(1000, 200), Some("aspect ratio above 4:1")),
+            (b"GIF89a\x01\0\x01\0".to_vec(), Some("only JPEG, PNG and WebP")),
+        ];
+
+        for (data, expected) in cases {
+            match (inspect_header_image(&data, "header.jpg"), expected) {
+                (Ok(()), None) => {}
+                (Err(error), Some(expected)) => assert!(error.contains(expected), "{}", error),
+                (result, expected) => panic!("got {:?}, expected {:?}", result, expected),
+            }
+        }
+    }
+
+    #[test]
+    fn flatten_onto_white_blends_by_alpha() {
+        let image = RgbaImage::from_fn(3, 1, |x, _| match x {
+            0 => Rgba([0, 0, 0, 0]),
+            1 => Rgba([0, 0, 0, 255]),
+            _ => Rgba([255, 0, 0, 128]),
+        });
+        let rgb = flatten_onto_white(&DynamicImage::ImageRgba8(image));
+        assert_eq!(rgb.pixels().copied().collect::<Vec<_>>(), vec![Rgb([255, 255, 255]), Rgb([0, 0, 0]), Rgb([255, 127, 127])]);
+    }
+
+    #[test]
+    fn encode_jpeg_to_fit_lowers_quality_until_it_fits() {
+        // Noise compresses poorly, so every quality step gives a smaller file.
+        let mut seed: u32 = 1;
+        let image = RgbImage::from_fn(300, 300, |_, _| {
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            Rgb((seed >> 8).to_le_bytes()[..3].try_into().unwrap())
+        });
+        let size_at = |quality: u8| encode_jpeg(&image, quality).unwrap().len();
+
+        let cases = [
+            (usize::MAX, Some(90)),
+            (size_at(90), Some(90)),
+            (size_at(90) - 1, Some(80)),
+            (size_at(70), Some(70)),
+            (size_at(60), Some(60)),
+            (size_at(60) - 1, None),
+        ];
+
+        for (max_size, expected) in cases {
+            let fitted = encode_jpeg_to_fit(&image, max_size).unwrap();
+            assert!(fitted.as_ref().is_none_or(|(jpeg, _)| jpeg.len() <= max_size), "{}", max_size);
+            assert_eq!(fitted.map(|(_, quality)| quality), expected, "{}", max_size);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::buttons::TemplateButton;
+
+pub const MAX_OFFER_TEXT_LENGTH: usize = 16;
+pub const MAX_OFFER_BODY_LENGTH: usize = 600;
+
+/// The offer header of a limited-time-offer template. The actual expiration
+/// timestamp is only sent with each message, so the template carries an example.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LimitedTimeOffer {
+    pub text: String,
+    #[serde(default)]
+    pub has_expiration: bool,
+    /// Example expiration, in milliseconds since the Unix epoch.
+    pub expiration_example: Option<i64>,
+}
+
+impl LimitedTimeOffer {
+    /// Checks the offer together with the parts of the template it constrains.
+    pub fn validate(&self, content: &str, buttons: &[TemplateButton], footer: Option<&str>) -> Result<(), String> {
+        if self.text.trim().is_empty() {
+            return Err("Limited-time offer text cannot be empty".to_string());
+        }
+        if self.text.chars().count() > MAX_OFFER_TEXT_LENGTH {
+            return Err(format!("Limited-time offer text is longer than {} characters", MAX_OFFER_TEXT_LENGTH));
+        }
+        if self.text.contains("{{") {
+            return Err("Limited-time offer text cannot contain variables".to_string());
+        }
+        if content.chars().count() > MAX_OFFER_BODY_LENGTH {
+            return Err(format!("Limited-time offer body is longer than {} characters", MAX_OFFER_BODY_LENGTH));
+        }
+        if footer.is_some() {
+            return Err("Limited-time offer templates cannot have a footer".to_string());
+        }
+
+        if self.has_expiration {
+            match self.expiration_example {
+                Some(timestamp) if timestamp > 0 => {}
+                _ => return Err("Limited-time offers with expiration need an example expiration timestamp".to_string()),
+            }
+        } else if self.expiration_example.is_some() {
+            return Err("Expiration example given but the offer has no expiration".to_string());
+        }
+
+        let url_buttons = buttons.iter().filter(|b| matches!(b, TemplateButton::Url { .. })).count();
+        let copy_code_position = buttons.iter().position(|b| matches!(b, TemplateButton::CopyCode { .. }));
+        if buttons.iter().any(|b| !matches!(b, TemplateButton::Url { .. } | TemplateButton::CopyCode { .. })) {
+            return Err("Limited-time offer templates only take copy code and URL buttons".to_string());
+        }
+        if url_buttons != 1 {
+            return Err("Limited-time offer templates need exactly one URL button".to_string());
+        }
+        match copy_code_position {
+            Some(0) => {}
+            Some(_) => return Err("The copy code button must be the first button of a limited-time offer".to_string()),
+            None if self.has_expiration => {
+                return Err("Limited-time offers with expiration need a copy code button for the coupon".to_string())
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use super::gupshup::TemplateType;
+
+const MB: usize = 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum MediaKind {
+    #[serde(rename = "IMAGE")]
+    Image,
+    #[serde(rename = "VIDEO")]
+    Video,
+    #[serde(rename = "DOCUMENT")]
+    Document,
+}
+
+impl MediaKind {
+    pub fn for_template_type(template_type: &TemplateType) -> Option<Self> {
+        match template_type {
+            TemplateType::Image => Some(MediaKind::Image),
+            TemplateType::Video => Some(MediaKind::Video),
+            TemplateType::Document => Some(MediaKind::Document),
+            _ => None,
+        }
+    }
+
+    /// Maximum file size WhatsApp accepts for this kind of header media.
+    pub fn max_size(&self) -> usize {
+        match self {
+            MediaKind::Image => 5 * MB,
+            MediaKind::Video => 16 * MB,
+            MediaKind::Document => 100 * MB,
+        }
+    }
+
+    pub fn allowed_mime_types(&self) -> &'static [&'static str] {
+        match self {
+            MediaKind::Image => &["image/jpeg", "image/png"],
+            MediaKind::Video => &["video/mp4", "video/3gpp"],
+            MediaKind::Document => &["application/pdf"],
+        }
+    }
+
+    pub fn default_file_name(&self) -> &'static str {
+        match self {
+            MediaKind::Image => "image.jpg",
+            MediaKind::Video => "video.mp4",
+            MediaKind::Document => "document.pdf",
+        }
+    }
+}
+
+/// Major brands of the ISO media files WhatsApp plays as MP4. QuickTime, HEIC, AVIF and
+/// M4A files use the same container with other brands and are rejected.
+const MP4_BRANDS: [&[u8; 4]; 11] = [
+    b"isom", b"iso2", b"iso4", b"iso5", b"iso6", b"mp41", b"mp42", b"avc1", b"M4V ", b"mmp4", b"dash",
+];
+
+/// The major brand of an ISO media file (MP4, QuickTime, HEIC...), found after `ftyp`.
+fn iso_brand(data: &[u8]) -> Option<&[u8]> {
+    (data.len() >= 12 && &data[4..8] == b"ftyp").then(|| &data[8..12])
+}
+
+fn sniff_mime(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return Some("image/jpeg");
+    }
+    if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        return Some("image/png");
+    }
+    if data.starts_with(b"%PDF") {
+        return Some("application/pdf");
+    }
+    if let Some(brand) = iso_brand(data) {
+        if brand.starts_with(b"3gp") || brand.starts_with(b"3g2") {
+            return Some("video/3gpp");
+        }
+        if MP4_BRANDS.iter().any(|mp4_brand| mp4_brand[..] == *brand) {
+            return Some("video/mp4");
+        }
+    }
+    None
+}
+
+fn mime_from_extension(file_name: &str) -> Option<&'static str> {
+    let extension = file_name.rsplit_once('.')?.1.to_lowercase();
+    match extension.as_str() {
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "png" => Some("image/png"),
+        "mp4" => Some("video/mp4"),
+        "3gp" | "3gpp" => Some("video/3gpp"),
+        "pdf" => Some("application/pdf"),
+        _ => None,
+    }
+}
+
+/// Detects the MIME type from the file contents, falling back to the file extension.
+/// ISO media files are never typed by extension, so a renamed `.mov` is not taken for an MP4.
+pub fn detect_mime(data: &[u8], file_name: &str) -> Option<&'static str> {
+    if iso_brand(data).is_some() {
+        return sniff_mime(data);
+    }
+    sniff_mime(data).or_else(|| mime_from_extension(file_name))
+}
+
+/// Checks the file against WhatsApp's rules for the given header kind and returns its MIME type.
+pub fn validate_media(kind: MediaKind, data: &[u8], file_name: &str) -> Result<&'static str, String> {
+    if data.is_empty() {
+        return Err(format!("File {} is empty", file_name));
+    }
+
+    let mime = detect_mime(data, file_name).ok_or_else(|| match iso_brand(data) {
+        Some(brand) => format!(
+            "File {} is an ISO media file of brand '{}', only MP4 and 3GPP videos are accepted",
+            file_name, String::from_utf8_lossy(brand).trim_end()
+        ),
+        None => format!("Could not detect the file type of {}", file_name),
+    })?;
+
+    if !kind.allowed_mime_types().contains(&mime) {
+        return Err(format!(
+            "File {} is {}, but {:?} headers accept only {}",
+            file_name, mime, kind, kind.allowed_mime_types().join(", ")
+        ));
+    }
+
+    if data.len() > kind.max_size() {
+        return Err(format!(
+            "File {} is {} bytes, above the {} MB limit for {:?} headers",
+            file_name, data.len(), kind.max_size() / MB, kind
+        ));
+    }
+
+    Ok(mime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso_file(brand: &[u8; 4]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, 0x18];
+        data.extend_from_slice(b"ftyp");
+        data.extend_from_slice(brand);
+        data.extend_from_slice(&[0; 12]);
+        data
+    }
+
+    #[test]
+    fn detect_mime_cases() {
+        let cases = [
+            (vec![0xFF, 0xD8, 0xFF, 0xE0], "photo.png", Some("image/jpeg")),
+            (b"\x89PNG\r\n\x1a\n....".to_vec(), "photo.jpg", Some("image/png")),
+            (b"%PDF-1.7".to_vec(), "file.pdf", Some("application/pdf")),
+            (iso_file(b"isom"), "clip.bin", Some("video/mp4")),
+            (iso_file(b"mp42"), "clip.mp4", Some("video/mp4")),
+            (iso_file(b"M4V "), "clip.m4v", Some("video/mp4")),
+            (iso_file(b"3gp5"), "clip.3gp", Some("video/3gpp")),
+            (iso_file(b"qt  "), "clip.mp4", None),
+            (iso_file(b"heic"), "photo.mp4", None),
+            (iso_file(b"avif"), "photo.jpg", None),
+            (iso_file(b"M4A "), "audio.mp4", None),
+            (b"unknown".to_vec(), "clip.mp4", Some("video/mp4")),
+            (b"unknown".to_vec(), "notes.txt", None),
+        ];
+
+        for (data, file_name, expected) in cases {
+            assert_eq!(detect_mime(&data, file_name), expected, "{}", file_name);
+        }
+    }
+
+    #[test]
+    fn quicktime_is_rejected_as_video_header() {
+        let error = validate_media(MediaKind::Video, &iso_file(b"qt  "), "clip.mov").unwrap_err();
+        assert!(error.contains("'qt'"), "{}", error);
+        assert_eq!(validate_media(MediaKind::Video, &iso_file(b"isom"), "clip.mp4"), Ok("video/mp4"));
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::gupshup::MediaDetails;
+use crate::storage::files::{content_hash, now_ms, write_atomic};
+
+pub const MEDIA_CACHE_FILE: &str = "media_cache.json";
+/// Gupshup media handles are not permanent, so older entries are uploaded again.
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+fn cache_key(app_id: &str, data: &[u8]) -> String {
+    format!("{}:{}", app_id, content_hash(data))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct CachedMedia {
+    app_id: String,
+    media: MediaDetails,
+    uploaded_at_ms: u64,
+}
+
+impl CachedMedia {
+    fn is_expired(&self) -> bool {
+        now_ms().saturating_sub(self.uploaded_at_ms) > MAX_AGE.as_millis() as u64
+    }
+}
+
+/// Media handles already uploaded to an app_id, keyed by app_id and content hash and
+/// persisted to a JSON file so they survive restarts.
+pub struct MediaCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<String, CachedMedia>>,
+    /// Held while the file is written, so saves finish in the order they read the entries.
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl MediaCache {
+    /// Loads the cache from disk, starting empty when the file is missing or unreadable.
+    pub fn load(path: PathBuf) -> Self {
+        let entries: HashMap<String, CachedMedia> = match fs::read(&path) {
+            Ok(contents) => serde_json::from_slice(&contents).unwrap_or_else(|e| {
+                println!("Ignoring unreadable media cache {}: {}", path.display(), e);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        let entries = entries.into_iter().filter(|(_, entry)| !entry.is_expired()).collect();
+
+        MediaCache {
+            path,
+            entries: Mutex::new(entries),
+            saving: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn get(&self, app_id: &str, data: &[u8]) -> Option<MediaDetails> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(&cache_key(app_id, data))
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| entry.media.clone())
+    }
+
+    pub async fn insert(&self, app_id: &str, data: &[u8], media: &MediaDetails) {
+        self.entries.lock().unwrap().insert(cache_key(app_id, data), CachedMedia {
+            app_id: app_id.to_string(),
+            media: media.clone(),
+            uploaded_at_ms: now_ms(),
+        });
+        self.save().await;
+    }
+
+    /// Drops every handle of the app_id, used once Gupshup stops accepting one of them.
+    pub async fn forget_app(&self, app_id: &str) {
+        self.entries.lock().unwrap().retain(|_, entry| entry.app_id != app_id);
+        self.save().await;
+    }
+
+    /// Writes the entries to disk without holding their lock, so lookups never wait on the file.
+    async fn save(&self) {
+        let _saving = self.saving.lock().await;
+        let contents = serde_json::to_vec(&*self.entries.lock().unwrap());
+        let result = match contents {
+            Ok(contents) => {
+                if let Some(parent) = self.path.parent() {
+                    if let Err(e) = tokio::fs::create_dir_all(parent).await {
+                        println!("Error creating media cache directory {}: {}", parent.display(), e);
+                    }
+                }
+                write_atomic(&self.path, &contents).await.map_err(|e| e.message)
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            println!("Error writing media cache {}: {}", self.path.display(), e);
+        }
+    }
+}
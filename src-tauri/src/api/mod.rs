@@ -1 +1,11 @@
-pub mod gupshup;
\ No newline at end of file
+pub mod authentication;
+pub mod buttons;
+pub mod carousel;
+pub mod gupshup;
+pub mod header_image;
+pub mod limited_offer;
+pub mod media;
+pub mod media_cache;
+pub mod retry;
+pub mod validation;
+pub mod variables;
\ No newline at end of file
@@ -0,0 +1,293 @@
+use serde::Serialize;
+
+use super::gupshup::{TemplateCategory, TemplateRequest, MAX_FOOTER_LENGTH};
+use super::variables::{self, TextPart};
+
+pub const MAX_ELEMENT_NAME_LENGTH: usize = 512;
+pub const MAX_BODY_LENGTH: usize = 1024;
+pub const MAX_HEADER_LENGTH: usize = 60;
+pub const MAX_CONSECUTIVE_NEWLINES: usize = 2;
+/// Meta rejects bodies that are mostly variables; this is the minimum number of
+/// fixed words the body needs for each variable it contains.
+pub const MIN_WORDS_PER_VARIABLE: usize = 2;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationCode {
+    ElementNameEmpty,
+    ElementNameInvalidCharacters,
+    ElementNameTooLong,
+    BodyEmpty,
+    BodyTooLong,
+    HeaderTooLong,
+    FooterTooLong,
+    InvalidVariables,
+    VariableAtStart,
+    VariableAtEnd,
+    TooManyVariables,
+    ConsecutiveNewlines,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Violation {
+    pub code: ViolationCode,
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl Violation {
+    fn new(code: ViolationCode, field: &'static str, message: String) -> Self {
+        Violation { code, field, message }
+    }
+}
+
+fn check_element_name(name: &str, violations: &mut Vec<Violation>) {
+    if name.is_empty() {
+        violations.push(Violation::new(
+            ViolationCode::ElementNameEmpty,
+            "element_name",
+            "Template name cannot be empty".to_string(),
+        ));
+        return;
+    }
+    if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+        violations.push(Violation::new(
+            ViolationCode::ElementNameInvalidCharacters,
+            "element_name",
+            format!("Template name '{}' can only have lowercase letters, digits and underscores", name),
+        ));
+    }
+    if name.len() > MAX_ELEMENT_NAME_LENGTH {
+        violations.push(Violation::new(
+            ViolationCode::ElementNameTooLong,
+            "element_name",
+            format!("Template name is longer than {} characters", MAX_ELEMENT_NAME_LENGTH),
+        ));
+    }
+}
+
+fn check_body(content: &str, is_authentication: bool, violations: &mut Vec<Violation>) {
+    if content.trim().is_empty() {
+        violations.push(Violation::new(
+            ViolationCode::BodyEmpty,
+            "content",
+            "Template body cannot be empty".to_string(),
+        ));
+        return;
+    }
+
+    let length = content.chars().count();
+    if length > MAX_BODY_LENGTH {
+        violations.push(Violation::new(
+            ViolationCode::BodyTooLong,
+            "content",
+            format!("Template body has {} characters, the limit is {}", length, MAX_BODY_LENGTH),
+        ));
+    }
+
+    let newlines = "\n".repeat(MAX_CONSECUTIVE_NEWLINES + 1);
+    if content.replace("\r\n", "\n").contains(&newlines) {
+        violations.push(Violation::new(
+            ViolationCode::ConsecutiveNewlines,
+            "content",
+            format!("Template body cannot have more than {} line breaks in a row", MAX_CONSECUTIVE_NEWLINES),
+        ));
+    }
+
+    if let Err(e) = variables::count_variables(content, TextPart::Body) {
+        violations.push(Violation::new(ViolationCode::InvalidVariables, "content", e.to_string()));
+        return;
+    }
+    // Authentication bodies are fixed by Meta around the code variable.
+    if is_authentication {
+        return;
+    }
+
+    let placeholders = variables::find_placeholders(content, TextPart::Body).unwrap_or_default();
+    if placeholders.is_empty() {
+        return;
+    }
+
+    let trimmed_start = content.len() - content.trim_start().len();
+    let trimmed_end = content.trim_end().len();
+    if placeholders.first().is_some_and(|p| p.start == trimmed_start) {
+        violations.push(Violation::new(
+            ViolationCode::VariableAtStart,
+            "content",
+            "Template body cannot start with a variable".to_string(),
+        ));
+    }
+    if placeholders.last().is_some_and(|p| p.end == trimmed_end) {
+        violations.push(Violation::new(
+            ViolationCode::VariableAtEnd,
+            "content",
+            "Template body cannot end with a variable".to_string(),
+        ));
+    }
+
+    let mut fixed_text = String::with_capacity(content.len());
+    let mut last = 0;
+    for placeholder in &placeholders {
+        fixed_text.push_str(&content[last..placeholder.start]);
+        fixed_text.push(' ');
+        last = placeholder.end;
+    }
+    fixed_text.push_str(&content[last..]);
+    let words = fixed_text.split_whitespace().count();
+    if words < placeholders.len() * MIN_WORDS_PER_VARIABLE {
+        violations.push(Violation::new(
+            ViolationCode::TooManyVariables,
+            "content",
+            format!(
+                "Template body has {} variables for {} words; add more text or remove variables",
+                placeholders.len(), words
+            ),
+        ));
+    }
+}
+
+fn check_header(header: &str, violations: &mut Vec<Violation>) {
+    let length = header.chars().count();
+    if length > MAX_HEADER_LENGTH {
+        violations.push(Violation::new(
+            ViolationCode::HeaderTooLong,
+            "header",
+            format!("Header has {} characters, the limit is {}", length, MAX_HEADER_LENGTH),
+        ));
+    }
+    if let Err(e) = variables::count_variables(header, TextPart::Header) {
+        violations.push(Violation::new(ViolationCode::InvalidVariables, "header", e.to_string()));
+    }
+}
+
+/// Runs the offline checks on a template before anything is sent to Gupshup.
+/// Checks a template name on its own, for operations that put it in a URL path.
+pub fn validate_element_name(name: &str) -> Result<(), String> {
+    let mut violations = Vec::new();
+    check_element_name(name, &mut violations);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations.into_iter().map(|v| v.message).collect::<Vec<_>>().join("; "))
+    }
+}
+
+pub fn validate_template(template: &TemplateRequest) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    check_element_name(&template.element_name, &mut violations);
+    let is_authentication = matches!(template.category, TemplateCategory::Authentication);
+    check_body(&template.content, is_authentication, &mut violations);
+    if let Some(ref header) = template.header {
+        check_header(header, &mut violations);
+    }
+    if let Some(ref footer) = template.footer {
+        let length = footer.chars().count();
+        if length > MAX_FOOTER_LENGTH {
+            violations.push(Violation::new(
+                ViolationCode::FooterTooLong,
+                "footer",
+                format!("Footer has {} characters, the limit is {}", length, MAX_FOOTER_LENGTH),
+            ));
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::gupshup::TemplateType;
+
+    fn template(name: &str, content: &str, category: TemplateCategory) -> TemplateRequest {
+        TemplateRequest::new(name, content, "app", category, TemplateType::Text, "vertical")
+    }
+
+    fn codes(template: &TemplateRequest) -> Vec<ViolationCode> {
+        validate_template(template).into_iter().map(|violation| violation.code).collect()
+    }
+
+    #[test]
+    fn element_name_cases() {
+        use ViolationCode::*;
+        let cases = [
+            ("welcome_2", vec![]),
+            ("", vec![ElementNameEmpty]),
+            ("Welcome", vec![ElementNameInvalidCharacters]),
+            ("hello-world", vec![ElementNameInvalidCharacters]),
+            ("promoção", vec![ElementNameInvalidCharacters]),
+            (&"a".repeat(MAX_ELEMENT_NAME_LENGTH), vec![]),
+            (&"a".repeat(MAX_ELEMENT_NAME_LENGTH + 1), vec![ElementNameTooLong]),
+        ];
+
+        for (name, expected) in cases {
+            let template = template(name, "Hello there, welcome to the store", TemplateCategory::Marketing);
+            assert_eq!(codes(&template), expected, "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn validate_element_name_rejects_path_characters() {
+        for name in ["welcome/../other", "welcome?force=true", "..", "welcome%2F"] {
+            assert!(validate_element_name(name).is_err(), "{:?}", name);
+        }
+        assert!(validate_element_name("welcome_2").is_ok());
+    }
+
+    #[test]
+    fn body_cases() {
+        use ViolationCode::*;
+        let cases = [
+            ("Hello there, welcome to the store".to_string(), vec![]),
+            ("".to_string(), vec![BodyEmpty]),
+            (" \n ".to_string(), vec![BodyEmpty]),
+            ("a".repeat(MAX_BODY_LENGTH), vec![]),
+            ("a".repeat(MAX_BODY_LENGTH + 1), vec![BodyTooLong]),
+            ("é".repeat(MAX_BODY_LENGTH), vec![]),
+            ("Line\n\nbreak".to_string(), vec![]),
+            ("Line\n\n\nbreak".to_string(), vec![ConsecutiveNewlines]),
+            ("Line\r\n\r\n\r\nbreak".to_string(), vec![ConsecutiveNewlines]),
+            ("{{1}} is your order number today".to_string(), vec![VariableAtStart]),
+            ("  {{1}} is your order number today".to_string(), vec![VariableAtStart]),
+            ("Your order number today is {{1}}".to_string(), vec![VariableAtEnd]),
+            ("Your order number today is {{1}} \n".to_string(), vec![VariableAtEnd]),
+            ("Your order number is {{1}}.".to_string(), vec![]),
+            ("Hello {{1}}".to_string(), vec![VariableAtEnd, TooManyVariables]),
+            ("Hi {{1}} and {{2}} ok".to_string(), vec![TooManyVariables]),
+            ("Hi {{1}} and {{2}} ok now".to_string(), vec![]),
+            ("Bad {{1} variable here".to_string(), vec![InvalidVariables]),
+            ("Gap {{1}} and {{3}} here".to_string(), vec![InvalidVariables]),
+        ];
+
+        for (content, expected) in cases {
+            let template = template("welcome", &content, TemplateCategory::Marketing);
+            assert_eq!(codes(&template), expected, "{:?}", content);
+        }
+    }
+
+    #[test]
+    fn authentication_body_may_start_with_the_code() {
+        let template = template("otp", "{{1}} is your verification code.", TemplateCategory::Authentication);
+        assert_eq!(codes(&template), vec![]);
+    }
+
+    #[test]
+    fn header_and_footer_cases() {
+        use ViolationCode::*;
+        let cases = [
+            (Some("Order {{1}}".to_string()), None, vec![]),
+            (Some("a".repeat(MAX_HEADER_LENGTH + 1)), None, vec![HeaderTooLong]),
+            (Some("{{1}} and {{2}}".to_string()), None, vec![InvalidVariables]),
+            (None, Some("a".repeat(MAX_FOOTER_LENGTH)), vec![]),
+            (None, Some("a".repeat(MAX_FOOTER_LENGTH + 1)), vec![FooterTooLong]),
+        ];
+
+        for (header, footer, expected) in cases {
+            let mut template = template("welcome", "Hello there, welcome to the store", TemplateCategory::Marketing);
+            template.header = header.clone();
+            template.footer = footer.clone();
+            assert_eq!(codes(&template), expected, "{:?} {:?}", header, footer);
+        }
+    }
+}
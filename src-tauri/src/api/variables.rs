@@ -0,0 +1,209 @@
+use serde::Serialize;
+use std::fmt;
+
+pub const MAX_HEADER_VARIABLES: usize = 1;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextPart {
+    Body,
+    Header,
+}
+
+impl fmt::Display for TextPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextPart::Body => write!(f, "body"),
+            TextPart::Header => write!(f, "header"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VariableError {
+    Malformed { part: TextPart, position: usize, placeholder: String },
+    NotSequential { part: TextPart, expected: u32, found: u32 },
+    TooManyVariables { part: TextPart, max: usize, found: usize },
+    ExampleCountMismatch { part: TextPart, expected: usize, received: usize },
+    EmptyExample { part: TextPart, variable: u32 },
+}
+
+impl fmt::Display for VariableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableError::Malformed { part, position, placeholder } => write!(
+                f, "Malformed variable '{}' at position {} of the {}. Use {{{{1}}}}, {{{{2}}}}, ...",
+                placeholder, position, part
+            ),
+            VariableError::NotSequential { part, expected, found } => write!(
+                f, "Variables in the {} must be sequential: expected {{{{{}}}}} but found {{{{{}}}}}",
+                part, expected, found
+            ),
+            VariableError::TooManyVariables { part, max, found } => write!(
+                f, "The {} can have at most {} variable(s), found {}", part, max, found
+            ),
+            VariableError::ExampleCountMismatch { part, expected, received } => write!(
+                f, "The {} has {} variable(s) but {} example value(s) were given", part, expected, received
+            ),
+            VariableError::EmptyExample { part, variable } => write!(
+                f, "Example value for {{{{{}}}}} in the {} is empty", variable, part
+            ),
+        }
+    }
+}
+
+/// A `{{n}}` occurrence in a template text, with its byte range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placeholder {
+    pub index: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Finds every `{{n}}` placeholder in the text, in order of appearance.
+pub fn find_placeholders(text: &str, part: TextPart) -> Result<Vec<Placeholder>, VariableError> {
+    let mut placeholders = Vec::new();
+    let mut offset = 0;
+
+    while let Some(found) = text[offset..].find("{{") {
+        let start = offset + found;
+        let Some(length) = text[start..].find("}}") else {
+            return Err(VariableError::Malformed {
+                part,
+                position: start,
+                placeholder: text[start..].chars().take(10).collect(),
+            });
+        };
+        let end = start + length + 2;
+        let inner = text[start + 2..end - 2].trim();
+
+        match inner.parse::<u32>() {
+            Ok(index) if index > 0 && inner.chars().all(|c| c.is_ascii_digit()) => {
+                placeholders.push(Placeholder { index, start, end });
+            }
+            _ => {
+                return Err(VariableError::Malformed {
+                    part,
+                    position: start,
+                    placeholder: text[start..end].to_string(),
+                })
+            }
+        }
+        offset = end;
+    }
+
+    Ok(placeholders)
+}
+
+/// Returns the number of distinct variables, checking they run from `{{1}}` without gaps.
+pub fn count_variables(text: &str, part: TextPart) -> Result<usize, VariableError> {
+    let mut indices: Vec<u32> = find_placeholders(text, part)?
+        .into_iter()
+        .map(|placeholder| placeholder.index)
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+
+    for (position, index) in indices.iter().enumerate() {
+        let expected = position as u32 + 1;
+        if *index != expected {
+            return Err(VariableError::NotSequential { part, expected, found: *index });
+        }
+    }
+
+    if part == TextPart::Header && indices.len() > MAX_HEADER_VARIABLES {
+        return Err(VariableError::TooManyVariables {
+            part,
+            max: MAX_HEADER_VARIABLES,
+            found: indices.len(),
+        });
+    }
+
+    Ok(indices.len())
+}
+
+/// Builds the example text Gupshup expects by replacing each `{{n}}` with the n-th example value.
+pub fn fill_examples(text: &str, part: TextPart, examples: &[String]) -> Result<String, VariableError> {
+    let expected = count_variables(text, part)?;
+    if examples.len() != expected {
+        return Err(VariableError::ExampleCountMismatch {
+            part,
+            expected,
+            received: examples.len(),
+        });
+    }
+    if let Some(position) = examples.iter().position(|example| example.trim().is_empty()) {
+        return Err(VariableError::EmptyExample { part, variable: position as u32 + 1 });
+    }
+
+    let mut filled = String::with_capacity(text.len());
+    let mut last = 0;
+    for placeholder in find_placeholders(text, part)? {
+        filled.push_str(&text[last..placeholder.start]);
+        filled.push_str(&examples[placeholder.index as usize - 1]);
+        last = placeholder.end;
+    }
+    filled.push_str(&text[last..]);
+
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn malformed(part: TextPart, position: usize, placeholder: &str) -> VariableError {
+        VariableError::Malformed { part, position, placeholder: placeholder.to_string() }
+    }
+
+    #[test]
+    fn count_variables_cases() {
+        use TextPart::{Body, Header};
+        let cases = [
+            ("No variables", Body, Ok(0)),
+            ("Hi {{1}}", Body, Ok(1)),
+            ("{{1}} and {{1}} again", Body, Ok(1)),
+            ("{{2}} comes before {{1}}", Body, Ok(2)),
+            ("Spaced {{ 1 }}", Body, Ok(1)),
+            ("Extra brace {{1}}}", Body, Ok(1)),
+            ("Gap {{1}} {{3}}", Body, Err(VariableError::NotSequential { part: Body, expected: 2, found: 3 })),
+            ("Starts at {{2}}", Body, Err(VariableError::NotSequential { part: Body, expected: 1, found: 2 })),
+            ("Hi {{1}", Body, Err(malformed(Body, 3, "{{1}"))),
+            ("{{1}} then {{2}", Body, Err(malformed(Body, 11, "{{2}"))),
+            ("{{0}}", Body, Err(malformed(Body, 0, "{{0}}"))),
+            ("{{name}}", Body, Err(malformed(Body, 0, "{{name}}"))),
+            ("{{+1}}", Body, Err(malformed(Body, 0, "{{+1}}"))),
+            ("{{{1}}", Body, Err(malformed(Body, 0, "{{{1}}"))),
+            ("Title {{1}}", Header, Ok(1)),
+            ("{{1}} {{2}}", Header, Err(VariableError::TooManyVariables { part: Header, max: 1, found: 2 })),
+        ];
+
+        for (text, part, expected) in cases {
+            assert_eq!(count_variables(text, part), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn fill_examples_cases() {
+        use TextPart::Body;
+        let cases = [
+            ("Hi {{1}}, order {{2}}", strings(&["Ana", "42"]), Ok("Hi Ana, order 42".to_string())),
+            ("{{1}} and {{1}}", strings(&["x"]), Ok("x and x".to_string())),
+            ("Olá {{1}}!", strings(&["João"]), Ok("Olá João!".to_string())),
+            ("No variables", strings(&[]), Ok("No variables".to_string())),
+            ("Hi {{1}}", strings(&[]), Err(VariableError::ExampleCountMismatch { part: Body, expected: 1, received: 0 })),
+            ("Hi", strings(&["extra"]), Err(VariableError::ExampleCountMismatch { part: Body, expected: 0, received: 1 })),
+            ("{{1}} {{2}}", strings(&["a", " "]), Err(VariableError::EmptyExample { part: Body, variable: 2 })),
+            ("Gap {{2}}", strings(&["a"]), Err(VariableError::NotSequential { part: Body, expected: 1, found: 2 })),
+        ];
+
+        for (text, examples, expected) in cases {
+            assert_eq!(fill_examples(text, Body, &examples), expected, "{:?}", text);
+        }
+    }
+}
@@ -1,22 +1,54 @@
 use dotenv::dotenv;
 use std::env;
+use std::time::Duration;
+
+use crate::api::retry::RetryPolicy;
+use crate::error::app_error::AppError;
 
 pub struct EnvVars {
     pub db_url: String,
     pub apikey: String,
     pub cookie: String,
+    pub retry: RetryPolicy,
+}
+
+fn var(name: &str) -> Result<String, AppError> {
+    env::var(name).map_err(|_| AppError::config(format!("No {} found in the .env file!", name)))
+}
+
+/// Reads an optional numeric variable, so `RETRY_MAX_ATTEMPTS=abc` fails loudly instead
+/// of silently falling back to the default.
+fn optional_number(name: &str) -> Result<Option<u64>, AppError> {
+    match env::var(name) {
+        Ok(value) => value.trim().parse().map(Some)
+            .map_err(|_| AppError::config(format!("{} must be a number, got '{}'", name, value))),
+        Err(_) => Ok(None),
+    }
 }
 
-pub fn load() -> EnvVars {
+fn retry_policy() -> Result<RetryPolicy, AppError> {
+    let mut retry = RetryPolicy::default();
+    if let Some(max_attempts) = optional_number("RETRY_MAX_ATTEMPTS")? {
+        retry.max_attempts = max_attempts.max(1) as u32;
+    }
+    if let Some(base_delay_ms) = optional_number("RETRY_BASE_DELAY_MS")? {
+        retry.base_delay = Duration::from_millis(base_delay_ms);
+    }
+    Ok(retry)
+}
+
+pub fn load() -> Result<EnvVars, AppError> {
     dotenv().ok();
 
-    let db_url = env::var("DB_URL").expect("No DB_URL found in the .env file!");
-    let apikey = env::var("APIKEY").expect("No APIKEY found in the .env file!");
-    let cookie = env::var("COOKIE").expect("No COOKIE found in the .env file!");
+    let db_url = var("DB_URL")?;
+    let apikey = var("APIKEY")?;
+    let cookie = var("COOKIE")?;
+    let retry = retry_policy()?;
 
-    EnvVars {
+    Ok(EnvVars {
         db_url,
         apikey,
         cookie,
-    }
+        retry,
+    })
 }
\ No newline at end of file
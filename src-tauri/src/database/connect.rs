@@ -1,6 +1,8 @@
-use sqlx::{postgres::PgConnection, Error, Connection};
+use sqlx::{postgres::PgConnection, Connection};
 
-pub async fn connect_db(db_url: &str) -> Result<PgConnection, Error> {
+use crate::error::app_error::AppError;
+
+pub async fn connect_db(db_url: &str) -> Result<PgConnection, AppError> {
     println!("Attempting to connect to database...");
     let conn = PgConnection::connect(db_url).await?;
     println!("Database connection established successfully");
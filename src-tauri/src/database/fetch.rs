@@ -1,4 +1,6 @@
-use sqlx::{Error, PgConnection, Row};
+use sqlx::{PgConnection, Row};
+
+use crate::error::app_error::AppError;
 
 #[derive(Debug)]
 pub struct ConnectionData {
@@ -15,7 +17,7 @@ pub struct ConnectionData {
     pub facebook_token: Option<String>,
 }
 
-pub async fn fetch_connections(conn: &mut PgConnection) -> Result<Vec<ConnectionData>,Error> {
+pub async fn fetch_connections(conn: &mut PgConnection) -> Result<Vec<ConnectionData>, AppError> {
     println!("Executing database query: SELECT * FROM parametros");
     let query = String::from(
         r#"SELECT * FROM parametros"#
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+
+use super::fetch::ConnectionData;
+
+/// Narrows the `parametros` rows a bulk run targets. Every field is optional and the
+/// set ones must all match. Value lists compare case-insensitively, and an empty string
+/// in them matches connections without a value.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConnectionFilter {
+    pub qualidade: Option<Vec<String>>,
+    pub restriction: Option<Vec<String>>,
+    pub min_issues: Option<i32>,
+    pub max_issues: Option<i32>,
+    pub msg_limit: Option<Vec<String>>,
+    /// Matched against `source_name`, case-insensitively, where `*` stands for any text.
+    pub source_name_pattern: Option<String>,
+    /// When set, only these app_ids are targeted.
+    pub include_app_ids: Option<Vec<String>>,
+    #[serde(default)]
+    pub exclude_app_ids: Vec<String>,
+}
+
+impl ConnectionFilter {
+    pub fn matches(&self, connection: &ConnectionData) -> bool {
+        let app_id = connection.app_id.as_deref().unwrap_or_default();
+
+        one_of(&self.qualidade, &connection.qualidade)
+            && one_of(&self.restriction, &connection.restriction)
+            && one_of(&self.msg_limit, &connection.msg_limit)
+            && self.min_issues.is_none_or(|min| connection.issues >= min)
+            && self.max_issues.is_none_or(|max| connection.issues <= max)
+            && self.source_name_pattern.as_ref().is_none_or(|pattern| {
+                wildcard_match(&pattern.to_lowercase(), &connection.source_name.as_deref().unwrap_or_default().to_lowercase())
+            })
+            && self.include_app_ids.as_ref().is_none_or(|include| include.iter().any(|id| id == app_id))
+            && !self.exclude_app_ids.iter().any(|id| id == app_id)
+    }
+
+    pub fn apply(&self, connections: Vec<ConnectionData>) -> Vec<ConnectionData> {
+        connections.into_iter()
+            .filter(|connection| self.matches(connection))
+            .collect()
+    }
+}
+
+fn one_of(allowed: &Option<Vec<String>>, value: &Option<String>) -> bool {
+    let value = value.as_deref().unwrap_or_default().trim();
+    allowed.as_ref().is_none_or(|allowed| allowed.iter().any(|candidate| candidate.trim().eq_ignore_ascii_case(value)))
+}
+
+/// Matches `text` against a pattern where `*` stands for any run of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_match_cases() {
+        let cases = [
+            ("store", "store", true),
+            ("store", "stores", false),
+            ("", "", true),
+            ("", "store", false),
+            ("*", "", true),
+            ("*", "anything", true),
+            ("store*", "store_sp", true),
+            ("store*", "my_store", false),
+            ("*_sp", "store_sp", true),
+            ("*_sp", "store_rj", false),
+            ("ab*ba", "abba", true),
+            ("ab*ba", "aba", false),
+            ("ab*ba", "abXba", true),
+            ("a*b*c", "aXbYc", true),
+            ("a*b*c", "aXcYb", false),
+            ("a*b*b*c", "abc", false),
+            ("a**c", "ac", true),
+            ("*loja*", "minha_loja_sp", true),
+        ];
+
+        for (pattern, text, expected) in cases {
+            assert_eq!(wildcard_match(pattern, text), expected, "{:?} against {:?}", pattern, text);
+        }
+    }
+
+    #[test]
+    fn one_of_cases() {
+        let allowed = Some(vec!["SP".to_string(), " rj ".to_string(), "".to_string()]);
+        let cases = [
+            (&allowed, Some("sp"), true),
+            (&allowed, Some("RJ"), true),
+            (&allowed, Some("MG"), false),
+            (&allowed, None, true),
+            (&None, Some("MG"), true),
+        ];
+
+        for (allowed, value, expected) in cases {
+            assert_eq!(one_of(allowed, &value.map(str::to_string)), expected, "{:?} in {:?}", value, allowed);
+        }
+    }
+}
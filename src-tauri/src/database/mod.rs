@@ -1,2 +1,3 @@
 pub mod connect;
 pub mod fetch;
+pub mod filter;
\ No newline at end of file
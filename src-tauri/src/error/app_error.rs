@@ -0,0 +1,325 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+use crate::api::variables::VariableError;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    TemplateAlreadyExists,
+    TemplateNotFound,
+    InvalidParameter,
+    InvalidCategory,
+    PolicyViolation,
+    Unauthorized,
+    RateLimited,
+    MediaRejected,
+    ServerError,
+    GupshupError,
+    Http,
+    Validation,
+    Database,
+    Config,
+    Storage,
+}
+
+/// What a bulk run should do with a connection that failed with a given code.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailureAction {
+    Skip,
+    Retry,
+    Abort,
+}
+
+impl ErrorCode {
+    pub fn action(&self) -> FailureAction {
+        match self {
+            ErrorCode::TemplateAlreadyExists => FailureAction::Skip,
+            ErrorCode::RateLimited | ErrorCode::ServerError | ErrorCode::Http => FailureAction::Retry,
+            _ => FailureAction::Abort,
+        }
+    }
+}
+
+/// The error every backend function and Tauri command returns. It reaches the
+/// frontend as `{ code, message, app_id, http_status, raw, attempts, details }`, where
+/// `raw` is the untouched Gupshup response body.
+#[derive(Debug, Serialize, Clone)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub app_id: Option<String>,
+    pub http_status: Option<u16>,
+    pub raw: Option<String>,
+    /// How many times the request was sent before giving up.
+    pub attempts: Option<u32>,
+    /// Structured data about the failure, such as the variable error a validation
+    /// failed with, so the frontend does not have to parse `message`. Boxed to keep
+    /// the error small, since nearly every function returns it.
+    pub details: Option<Box<Value>>,
+    /// The wait Gupshup asked for in a `Retry-After` header.
+    #[serde(skip)]
+    pub retry_after: Option<Duration>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        AppError {
+            code,
+            message: message.into(),
+            app_id: None,
+            http_status: None,
+            raw: None,
+            attempts: None,
+            details: None,
+            retry_after: None,
+        }
+    }
+
+    pub fn with_app_id(mut self, app_id: &str) -> Self {
+        self.app_id = Some(app_id.to_string());
+        self
+    }
+
+    pub fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Adds a field to the details, such as the language or card an error belongs to.
+    pub fn with_detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        let details = self.details.get_or_insert_with(|| Box::new(Value::Object(Default::default())));
+        if let Some(details) = details.as_object_mut() {
+            details.insert(key.to_string(), value.into());
+        }
+        self
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        AppError::new(ErrorCode::Validation, message)
+    }
+
+    pub fn config(message: impl Into<String>) -> Self {
+        AppError::new(ErrorCode::Config, message)
+    }
+
+    pub fn database(message: impl Into<String>) -> Self {
+        AppError::new(ErrorCode::Database, message)
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        AppError::new(ErrorCode::Storage, message)
+    }
+
+    /// Builds the error for a failed Gupshup response from its HTTP status and body.
+    /// Only error bodies are classified: a successful response that did not parse says
+    /// nothing about the failure, and its content would match the error keywords.
+    pub fn gupshup(app_id: &str, status: reqwest::StatusCode, body: &str) -> Self {
+        let (code, message) = if is_error_body(status, body) {
+            let message = response_message(body);
+            (classify(status.as_u16(), &message), message)
+        } else {
+            (ErrorCode::GupshupError, "Unexpected response from Gupshup".to_string())
+        };
+
+        AppError {
+            code,
+            message,
+            app_id: Some(app_id.to_string()),
+            http_status: Some(status.as_u16()),
+            raw: Some(body.to_string()),
+            attempts: None,
+            details: None,
+            retry_after: None,
+        }
+    }
+
+    pub fn is(&self, code: ErrorCode) -> bool {
+        self.code == code
+    }
+
+    /// Whether Gupshup refused a media handle the template referenced. A dead handle can
+    /// come back under other codes, like "Media not found" classified as TemplateNotFound,
+    /// so the message decides for every code that does not say the request itself failed.
+    pub fn rejects_media(&self) -> bool {
+        match self.code {
+            ErrorCode::MediaRejected => true,
+            ErrorCode::Unauthorized | ErrorCode::RateLimited | ErrorCode::ServerError => false,
+            _ => self.message.to_lowercase().contains("media"),
+        }
+    }
+}
+
+/// Whether the body reports an error, either through the HTTP status or the
+/// `{"status": "error"}` Gupshup sends with some 2xx responses.
+fn is_error_body(status: reqwest::StatusCode, body: &str) -> bool {
+    !status.is_success() || serde_json::from_str::<Value>(body)
+        .is_ok_and(|value| value.get("status").and_then(Value::as_str) == Some("error"))
+}
+
+/// Extracts the human readable message from a Gupshup error body, which is either
+/// `{"status": "error", "message": "..."}`, a nested `{"message": {"message": "..."}}`
+/// or plain text.
+fn response_message(body: &str) -> String {
+    let value = match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(value) => value,
+        Err(_) if body.trim().is_empty() => return "Unknown error".to_string(),
+        Err(_) => return body.trim().to_string(),
+    };
+
+    let message = value.get("message")
+        .or_else(|| value.get("error"))
+        .map(|message| message.get("message").unwrap_or(message));
+
+    match message {
+        Some(serde_json::Value::String(message)) => message.clone(),
+        Some(message) => message.to_string(),
+        None => body.trim().to_string(),
+    }
+}
+
+fn classify(status: u16, message: &str) -> ErrorCode {
+    // The status is authoritative for auth, throttling and server failures, whatever the body says.
+    match status {
+        401 | 403 => return ErrorCode::Unauthorized,
+        429 => return ErrorCode::RateLimited,
+        500.. => return ErrorCode::ServerError,
+        _ => {}
+    }
+
+    let lower = message.to_lowercase();
+    if lower.contains("already exists") {
+        ErrorCode::TemplateAlreadyExists
+    } else if status == 404 || lower.contains("does not exist") || lower.contains("not found") {
+        ErrorCode::TemplateNotFound
+    } else if lower.contains("unauthorized") || lower.contains("session") || lower.contains("login") {
+        ErrorCode::Unauthorized
+    } else if lower.contains("too many requests") || lower.contains("rate limit") {
+        ErrorCode::RateLimited
+    } else if lower.contains("category") {
+        ErrorCode::InvalidCategory
+    } else if lower.contains("policy") || lower.contains("violat") || lower.contains("abusive") {
+        ErrorCode::PolicyViolation
+    } else if status == 400 || lower.contains("invalid") {
+        ErrorCode::InvalidParameter
+    } else {
+        ErrorCode::GupshupError
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.app_id {
+            Some(ref app_id) => write!(f, "{} (app_id {})", self.message, app_id),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<VariableError> for AppError {
+    fn from(e: VariableError) -> Self {
+        AppError {
+            details: serde_json::to_value(&e).ok().map(Box::new),
+            ..AppError::validation(e.to_string())
+        }
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_decode() {
+            // The response arrived but was not what we expected, sending it again will not help.
+            return AppError::new(ErrorCode::GupshupError, format!("Unexpected response from Gupshup: {}", e));
+        }
+        AppError::new(ErrorCode::Http, format!("Request error: {}", e))
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(e: sqlx::Error) -> Self {
+        AppError::database(format!("Database error: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classify_cases() {
+        use ErrorCode::*;
+        let cases = [
+            (400, "Template already exists", TemplateAlreadyExists),
+            (200, "Template Already Exists with same name", TemplateAlreadyExists),
+            (404, "", TemplateNotFound),
+            (400, "Template does not exist", TemplateNotFound),
+            (401, "", Unauthorized),
+            (403, "Template already exists", Unauthorized),
+            (401, "Invalid category", Unauthorized),
+            (400, "Session expired, please login again", Unauthorized),
+            (429, "", RateLimited),
+            (429, "Template not found", RateLimited),
+            (400, "Rate limit reached", RateLimited),
+            (500, "", ServerError),
+            (502, "Template already exists", ServerError),
+            (503, "Invalid parameter", ServerError),
+            (400, "Invalid category for this template", InvalidCategory),
+            (400, "Template violates the commerce policy", PolicyViolation),
+            (400, "", InvalidParameter),
+            (422, "Invalid button url", InvalidParameter),
+            (200, "Something went wrong", GupshupError),
+        ];
+
+        for (status, message, expected) in cases {
+            assert_eq!(classify(status, message), expected, "{} {:?}", status, message);
+        }
+    }
+
+    #[test]
+    fn rejects_media_cases() {
+        use ErrorCode::*;
+        let cases = [
+            (MediaRejected, "Failed to upload media 'image.jpg'", true),
+            (TemplateNotFound, "Media not found", true),
+            (TemplateNotFound, "mediaId does not exist", true),
+            (InvalidParameter, "Invalid media handle", true),
+            (GupshupError, "Media expired", true),
+            (TemplateNotFound, "Template does not exist", false),
+            (InvalidParameter, "Invalid button url", false),
+            (Unauthorized, "Media access denied", false),
+            (RateLimited, "Too many media uploads", false),
+            (ServerError, "Media service unavailable", false),
+        ];
+
+        for (code, message, expected) in cases {
+            assert_eq!(AppError::new(code, message).rejects_media(), expected, "{:?} {:?}", code, message);
+        }
+
+        let error = AppError::gupshup("app", reqwest::StatusCode::BAD_REQUEST, r#"{"status":"error","message":"Media not found"}"#);
+        assert_eq!(error.code, ErrorCode::TemplateNotFound);
+        assert!(error.rejects_media());
+    }
+
+    #[test]
+    fn gupshup_only_classifies_error_bodies() {
+        use reqwest::StatusCode;
+        let listing = r#"{"status":"success","templates":[{"category":"MARKETING","data":"Your session was not found"}]}"#;
+        let cases = [
+            (StatusCode::OK, listing, ErrorCode::GupshupError),
+            (StatusCode::OK, "<html>Please login</html>", ErrorCode::GupshupError),
+            (StatusCode::OK, r#"{"status":"error","message":"Invalid category"}"#, ErrorCode::InvalidCategory),
+            (StatusCode::BAD_REQUEST, r#"{"status":"error","message":"Template already exists"}"#, ErrorCode::TemplateAlreadyExists),
+            (StatusCode::BAD_REQUEST, "Invalid category", ErrorCode::InvalidCategory),
+        ];
+
+        for (status, body, expected) in cases {
+            let error = AppError::gupshup("app", status, body);
+            assert_eq!(error.code, expected, "{} {}", status, body);
+            assert_eq!(error.raw.as_deref(), Some(body));
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod app_error;
\ No newline at end of file
@@ -0,0 +1,404 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use super::progress::ConnectionStatus;
+use crate::error::app_error::{AppError, ErrorCode};
+use crate::storage::files::{content_hash, now_ms, write_atomic};
+
+const JOURNAL_DIR: &str = "jobs";
+const MEDIA_DIR: &str = "media";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobTarget {
+    pub app_id: String,
+    pub source_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TargetOutcome {
+    pub status: ConnectionStatus,
+    pub message: Option<String>,
+}
+
+/// Everything needed to resume a bulk job: its parameters, the connections it targets
+/// and the outcome of every connection reached so far.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobRecord {
+    pub job_id: String,
+    pub template_name: String,
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+    /// The bulk command parameters, kept as JSON so the journal does not depend on them.
+    /// Media is taken out before and only referenced from `media`.
+    pub params: serde_json::Value,
+    /// The content hash of each media file of the job, keyed by where it belongs in the params.
+    #[serde(default)]
+    pub media: BTreeMap<String, String>,
+    pub targets: Vec<JobTarget>,
+    pub outcomes: BTreeMap<String, TargetOutcome>,
+    pub finished: bool,
+}
+
+impl JobRecord {
+    pub fn new(job_id: &str, template_name: &str, params: serde_json::Value, targets: Vec<JobTarget>) -> Self {
+        let now = now_ms();
+        JobRecord {
+            job_id: job_id.to_string(),
+            template_name: template_name.to_string(),
+            created_at_ms: now,
+            updated_at_ms: now,
+            params,
+            media: BTreeMap::new(),
+            targets,
+            outcomes: BTreeMap::new(),
+            finished: false,
+        }
+    }
+
+    /// The targets that were never reached or did not end with the template in place.
+    pub fn pending_targets(&self) -> Vec<JobTarget> {
+        self.targets.iter()
+            .filter(|target| !self.outcomes.get(&target.app_id).is_some_and(|outcome| outcome.status.is_done()))
+            .cloned()
+            .collect()
+    }
+
+    fn apply(&mut self, line: OutcomeLine) {
+        self.updated_at_ms = self.updated_at_ms.max(line.recorded_at_ms);
+        self.outcomes.insert(line.app_id, TargetOutcome { status: line.status, message: line.message });
+    }
+
+    fn summary(&self) -> JobSummary {
+        let pending = self.pending_targets().len();
+        JobSummary {
+            job_id: self.job_id.clone(),
+            template_name: self.template_name.clone(),
+            created_at_ms: self.created_at_ms,
+            updated_at_ms: self.updated_at_ms,
+            total: self.targets.len(),
+            done: self.targets.len() - pending,
+            pending,
+            finished: self.finished,
+        }
+    }
+}
+
+/// One line of the outcome log a running job appends to.
+#[derive(Debug, Serialize, Deserialize)]
+struct OutcomeLine {
+    app_id: String,
+    status: ConnectionStatus,
+    message: Option<String>,
+    recorded_at_ms: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct JobSummary {
+    pub job_id: String,
+    pub template_name: String,
+    pub created_at_ms: u64,
+    pub updated_at_ms: u64,
+    pub total: usize,
+    pub done: usize,
+    pub pending: usize,
+    pub finished: bool,
+}
+
+/// Job ids come from the frontend and end up in file names, so only the
+/// `job-<millis>-<counter>` ids `JobRegistry` hands out are accepted.
+fn check_job_id(job_id: &str) -> Result<(), AppError> {
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    let valid = job_id.strip_prefix("job-")
+        .and_then(|rest| rest.split_once('-'))
+        .is_some_and(|(millis, counter)| is_number(millis) && is_number(counter));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::new(ErrorCode::InvalidParameter, format!("'{}' is not a job id", job_id)))
+    }
+}
+
+fn check_media_hash(hash: &str) -> Result<(), AppError> {
+    if hash.len() == 64 && hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(AppError::storage(format!("Journal references invalid media '{}'", hash)))
+    }
+}
+
+/// Reads a job record and applies the outcomes logged since it was last written.
+/// A line cut short by a crash is ignored.
+async fn read_record(path: &Path) -> Result<JobRecord, String> {
+    let contents = fs::read(path).await.map_err(|e| e.to_string())?;
+    let mut record: JobRecord = serde_json::from_slice(&contents).map_err(|e| e.to_string())?;
+
+    match fs::read_to_string(path.with_extension("outcomes.jsonl")).await {
+        Ok(log) => {
+            for line in log.lines().filter_map(|line| serde_json::from_str::<OutcomeLine>(line).ok()) {
+                record.apply(line);
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.to_string()),
+    }
+    Ok(record)
+}
+
+/// One JSON file per bulk job under `<app data dir>/jobs`, next to the outcome log
+/// of the job while it runs. Media files are stored once under `jobs/media`, named
+/// after their content hash.
+pub struct Journal {
+    dir: PathBuf,
+}
+
+impl Journal {
+    pub async fn open(app: &AppHandle) -> Result<Self, AppError> {
+        let dir = app.path().app_data_dir()
+            .map_err(|e| AppError::storage(format!("Failed to resolve app data directory: {}", e)))?
+            .join(JOURNAL_DIR);
+        fs::create_dir_all(dir.join(MEDIA_DIR)).await
+            .map_err(|e| AppError::storage(format!("Failed to create journal directory {}: {}", dir.display(), e)))?;
+        Ok(Journal { dir })
+    }
+
+    fn path(&self, job_id: &str) -> Result<PathBuf, AppError> {
+        check_job_id(job_id)?;
+        Ok(self.dir.join(format!("{}.json", job_id)))
+    }
+
+    fn log_path(&self, job_id: &str) -> Result<PathBuf, AppError> {
+        Ok(self.path(job_id)?.with_extension("outcomes.jsonl"))
+    }
+
+    fn media_path(&self, hash: &str) -> Result<PathBuf, AppError> {
+        check_media_hash(hash)?;
+        Ok(self.dir.join(MEDIA_DIR).join(format!("{}.bin", hash)))
+    }
+
+    pub async fn save(&self, record: &JobRecord) -> Result<(), AppError> {
+        let path = self.path(&record.job_id)?;
+        let contents = serde_json::to_vec(record)
+            .map_err(|e| AppError::storage(format!("Failed to serialize job {}: {}", record.job_id, e)))?;
+        write_atomic(&path, &contents).await
+    }
+
+    pub async fn load(&self, job_id: &str) -> Result<JobRecord, AppError> {
+        let path = self.path(job_id)?;
+        if !fs::try_exists(&path).await.unwrap_or(false) {
+            return Err(AppError::new(ErrorCode::InvalidParameter, format!("No job with id {}", job_id)));
+        }
+
+        read_record(&path).await
+            .map_err(|e| AppError::storage(format!("Failed to read journal {}: {}", path.display(), e)))
+    }
+
+    /// Stores each media file unless a job already stored the same content, and returns
+    /// the content hashes to keep in `JobRecord::media`.
+    pub async fn store_media(&self, media: &BTreeMap<String, Vec<u8>>) -> Result<BTreeMap<String, String>, AppError> {
+        let mut hashes = BTreeMap::new();
+        for (slot, data) in media {
+            let hash = content_hash(data);
+            let path = self.media_path(&hash)?;
+            if !fs::try_exists(&path).await.unwrap_or(false) {
+                write_atomic(&path, data).await?;
+            }
+            hashes.insert(slot.clone(), hash);
+        }
+        Ok(hashes)
+    }
+
+    /// Reads back the media files a record references.
+    pub async fn load_media(&self, record: &JobRecord) -> Result<BTreeMap<String, Vec<u8>>, AppError> {
+        let mut media = BTreeMap::new();
+        for (slot, hash) in &record.media {
+            let path = self.media_path(hash)?;
+            let data = fs::read(&path).await
+                .map_err(|e| AppError::storage(format!("Failed to read media of job {} from {}: {}", record.job_id, path.display(), e)))?;
+            media.insert(slot.clone(), data);
+        }
+        Ok(media)
+    }
+
+    /// Every journaled job. Unreadable journals are skipped.
+    async fn records(&self) -> Result<Vec<JobRecord>, AppError> {
+        let read_error = |e: std::io::Error| AppError::storage(format!("Failed to read journal directory {}: {}", self.dir.display(), e));
+        let mut entries = fs::read_dir(&self.dir).await.map_err(read_error)?;
+
+        let mut records = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(read_error)? {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            match read_record(&path).await {
+                Ok(record) => records.push(record),
+                Err(e) => println!("Skipping unreadable journal {}: {}", path.display(), e),
+            }
+        }
+        Ok(records)
+    }
+
+    /// Summaries of every journaled job, newest first.
+    pub async fn list(&self) -> Result<Vec<JobSummary>, AppError> {
+        let mut summaries: Vec<JobSummary> = self.records().await?.iter().map(JobRecord::summary).collect();
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.created_at_ms));
+        Ok(summaries)
+    }
+
+    /// Deletes a job with its outcome log and the media no other job references.
+    pub async fn delete(&self, job_id: &str) -> Result<(), AppError> {
+        let record = self.load(job_id).await?;
+        let path = self.path(job_id)?;
+        fs::remove_file(&path).await
+            .map_err(|e| AppError::storage(format!("Failed to delete journal {}: {}", path.display(), e)))?;
+        let log_path = self.log_path(job_id)?;
+        match fs::remove_file(&log_path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => println!("Error removing outcome log {}: {}", log_path.display(), e),
+            _ => {}
+        }
+        self.remove_unreferenced_media(record.media.values()).await;
+        Ok(())
+    }
+
+    /// Deletes the given media files unless a journaled job still references them.
+    /// Failures are only logged, a leftover file is harmless.
+    async fn remove_unreferenced_media(&self, hashes: impl Iterator<Item = &String>) {
+        let referenced: HashSet<String> = match self.records().await {
+            Ok(records) => records.into_iter().flat_map(|record| record.media.into_values()).collect(),
+            Err(e) => {
+                println!("Keeping job media, the journals could not be read: {}", e);
+                return;
+            }
+        };
+        for hash in hashes.filter(|hash| !referenced.contains(*hash)) {
+            let removed = match self.media_path(hash) {
+                Ok(path) => fs::remove_file(&path).await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.message),
+            };
+            if let Err(e) = removed {
+                println!("Error removing job media {}: {}", hash, e);
+            }
+        }
+    }
+}
+
+/// The journal of a running job. Every outcome is appended to the outcome log as soon
+/// as it is known, and folded into the job record when the job finishes.
+pub struct JobWriter {
+    journal: Journal,
+    record: Mutex<JobRecord>,
+    log_path: PathBuf,
+    log: tokio::sync::Mutex<File>,
+}
+
+impl JobWriter {
+    /// Journals a new job, storing its media next to the journal.
+    pub async fn create(journal: Journal, mut record: JobRecord, media: &BTreeMap<String, Vec<u8>>) -> Result<Self, AppError> {
+        record.media = journal.store_media(media).await?;
+        JobWriter::start(journal, record).await
+    }
+
+    /// Writes the record with every outcome logged so far, then starts a new outcome log.
+    pub async fn start(journal: Journal, mut record: JobRecord) -> Result<Self, AppError> {
+        record.finished = false;
+        record.updated_at_ms = now_ms();
+        journal.save(&record).await?;
+
+        let log_path = journal.log_path(&record.job_id)?;
+        let log = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&log_path)
+            .await
+            .map_err(|e| AppError::storage(format!("Failed to open outcome log {}: {}", log_path.display(), e)))?;
+        Ok(JobWriter {
+            journal,
+            record: Mutex::new(record),
+            log_path,
+            log: tokio::sync::Mutex::new(log),
+        })
+    }
+
+    /// A failed write is logged instead of stopping the run, since the submissions themselves succeeded.
+    pub async fn record(&self, app_id: &str, status: ConnectionStatus, message: Option<String>) {
+        let line = OutcomeLine {
+            app_id: app_id.to_string(),
+            status,
+            message,
+            recorded_at_ms: now_ms(),
+        };
+        let mut contents = match serde_json::to_vec(&line) {
+            Ok(contents) => contents,
+            Err(e) => {
+                println!("Error serializing job outcome: {}", e);
+                return;
+            }
+        };
+        contents.push(b'\n');
+        self.record.lock().unwrap().apply(line);
+
+        let mut log = self.log.lock().await;
+        let written = match log.write_all(&contents).await {
+            Ok(()) => log.flush().await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            println!("Error writing job outcome to {}: {}", self.log_path.display(), e);
+        }
+    }
+
+    /// Writes the final record, after which the outcome log is no longer needed. Once
+    /// every target is done the job cannot be resumed, so its media is released too.
+    pub async fn finish(&self) {
+        let (record, released_media) = {
+            let mut record = self.record.lock().unwrap();
+            record.finished = true;
+            record.updated_at_ms = now_ms();
+            let released_media = if record.pending_targets().is_empty() {
+                std::mem::take(&mut record.media)
+            } else {
+                BTreeMap::new()
+            };
+            (record.clone(), released_media)
+        };
+        if let Err(e) = self.journal.save(&record).await {
+            println!("Error writing job journal: {}", e);
+            return;
+        }
+        if let Err(e) = fs::remove_file(&self.log_path).await {
+            println!("Error removing outcome log {}: {}", self.log_path.display(), e);
+        }
+        self.journal.remove_unreferenced_media(released_media.values()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_job_id_cases() {
+        let cases = [
+            ("job-1718000000000-0", true),
+            ("job-1-12", true),
+            ("job-1718000000000", false),
+            ("job--0", false),
+            ("job-1-", false),
+            ("job-1-2-3", false),
+            ("job-1-2.json", false),
+            ("../job-1-2", false),
+            ("job-1-../../etc", false),
+            ("", false),
+        ];
+
+        for (job_id, valid) in cases {
+            assert_eq!(check_job_id(job_id).is_ok(), valid, "{:?}", job_id);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod journal;
+pub mod progress;
+pub mod registry;
+pub mod runner;
\ No newline at end of file
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::app_error::AppError;
+
+pub const PROGRESS_EVENT: &str = "bulk-progress";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionStatus {
+    Created,
+    SkippedExists,
+    Failed,
+    NotAttempted,
+    Cancelled,
+}
+
+impl ConnectionStatus {
+    /// Whether the connection ended with the template in place and needs no resubmission.
+    pub fn is_done(&self) -> bool {
+        matches!(self, ConnectionStatus::Created | ConnectionStatus::SkippedExists)
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStage {
+    Started,
+    UploadingMedia,
+    Submitting,
+    Finished,
+}
+
+/// Running totals of a bulk run, sent with every progress event.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ProgressTotals {
+    pub total: usize,
+    pub started: usize,
+    pub completed: usize,
+    pub created: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub not_attempted: usize,
+    pub cancelled: usize,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProgressEvent {
+    pub job_id: String,
+    pub app_id: String,
+    pub source_name: Option<String>,
+    pub stage: ProgressStage,
+    pub language_code: Option<String>,
+    pub status: Option<ConnectionStatus>,
+    pub error: Option<AppError>,
+    pub totals: ProgressTotals,
+}
+
+/// Emits `bulk-progress` events for the connections of one bulk run.
+pub struct ProgressReporter {
+    app: AppHandle,
+    job_id: String,
+    totals: Mutex<ProgressTotals>,
+}
+
+impl ProgressReporter {
+    pub fn new(app: AppHandle, job_id: &str, total: usize) -> Self {
+        ProgressReporter {
+            app,
+            job_id: job_id.to_string(),
+            totals: Mutex::new(ProgressTotals { total, ..ProgressTotals::default() }),
+        }
+    }
+
+    pub fn connection<'a>(&'a self, app_id: &str, source_name: Option<&str>) -> ConnectionProgress<'a> {
+        ConnectionProgress {
+            reporter: self,
+            app_id: app_id.to_string(),
+            source_name: source_name.map(str::to_string),
+        }
+    }
+
+    fn emit(&self, event: ProgressEvent) {
+        if let Err(e) = self.app.emit(PROGRESS_EVENT, &event) {
+            println!("Error emitting bulk progress event: {}", e);
+        }
+    }
+}
+
+/// The progress of a single connection within a bulk run.
+pub struct ConnectionProgress<'a> {
+    reporter: &'a ProgressReporter,
+    app_id: String,
+    source_name: Option<String>,
+}
+
+impl ConnectionProgress<'_> {
+    pub fn started(&self) {
+        self.reporter.totals.lock().unwrap().started += 1;
+        self.emit(ProgressStage::Started, None, None, None);
+    }
+
+    pub fn uploading_media(&self) {
+        self.emit(ProgressStage::UploadingMedia, None, None, None);
+    }
+
+    pub fn submitting(&self, language_code: &str) {
+        self.emit(ProgressStage::Submitting, Some(language_code.to_string()), None, None);
+    }
+
+    pub fn finished(&self, status: ConnectionStatus, error: Option<&AppError>) {
+        {
+            let mut totals = self.reporter.totals.lock().unwrap();
+            totals.completed += 1;
+            match status {
+                ConnectionStatus::Created => totals.created += 1,
+                ConnectionStatus::SkippedExists => totals.skipped += 1,
+                ConnectionStatus::Failed => totals.failed += 1,
+                ConnectionStatus::NotAttempted => totals.not_attempted += 1,
+                ConnectionStatus::Cancelled => totals.cancelled += 1,
+            }
+        }
+        self.emit(ProgressStage::Finished, None, Some(status), error.cloned());
+    }
+
+    fn emit(
+        &self,
+        stage: ProgressStage,
+        language_code: Option<String>,
+        status: Option<ConnectionStatus>,
+        error: Option<AppError>,
+    ) {
+        let totals = self.reporter.totals.lock().unwrap().clone();
+        self.reporter.emit(ProgressEvent {
+            job_id: self.reporter.job_id.clone(),
+            app_id: self.app_id.clone(),
+            source_name: self.source_name.clone(),
+            stage,
+            language_code,
+            status,
+            error,
+            totals,
+        });
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Shared flag a running job checks before starting each connection.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// The bulk jobs currently running, kept in Tauri managed state so `cancel_job`
+/// can reach them.
+#[derive(Default)]
+pub struct JobRegistry {
+    jobs: Mutex<HashMap<String, CancelToken>>,
+    next_id: AtomicU64,
+}
+
+impl JobRegistry {
+    /// Registers a new job and returns its id with the token it should watch.
+    pub fn start(&self) -> (String, CancelToken) {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default();
+        let job_id = format!("job-{}-{}", millis, self.next_id.fetch_add(1, Ordering::SeqCst));
+        let token = CancelToken::default();
+
+        self.jobs.lock().unwrap().insert(job_id.clone(), token.clone());
+        (job_id, token)
+    }
+
+    /// Registers an existing job again to resume it. Returns `None` while it is still running.
+    pub fn resume(&self, job_id: &str) -> Option<CancelToken> {
+        let mut jobs = self.jobs.lock().unwrap();
+        if jobs.contains_key(job_id) {
+            return None;
+        }
+
+        let token = CancelToken::default();
+        jobs.insert(job_id.to_string(), token.clone());
+        Some(token)
+    }
+
+    /// Cancels a running job, returning false when no job has that id.
+    pub fn cancel(&self, job_id: &str) -> bool {
+        match self.jobs.lock().unwrap().get(job_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn is_running(&self, job_id: &str) -> bool {
+        self.jobs.lock().unwrap().contains_key(job_id)
+    }
+
+    pub fn finish(&self, job_id: &str) {
+        self.jobs.lock().unwrap().remove(job_id);
+    }
+}
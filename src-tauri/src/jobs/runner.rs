@@ -0,0 +1,272 @@
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use tauri::AppHandle;
+
+use super::journal::{JobTarget, JobWriter};
+use super::progress::{ConnectionProgress, ConnectionStatus, ProgressReporter};
+use super::registry::CancelToken;
+use crate::api::header_image::ImageReport;
+use crate::error::app_error::AppError;
+use crate::status::tracker::StatusTracker;
+
+pub const DEFAULT_BULK_CONCURRENCY: usize = 4;
+pub const MAX_BULK_CONCURRENCY: usize = 16;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LanguageStatus {
+    Created,
+    AlreadyExists,
+    Failed,
+}
+
+/// How one language version of a template ended on one app_id.
+#[derive(Serialize)]
+pub struct LanguageOutcome {
+    pub language_code: String,
+    pub status: LanguageStatus,
+    pub error: Option<AppError>,
+    pub attempts: u32,
+}
+
+#[derive(Serialize)]
+pub struct ConnectionOutcome {
+    app_id: String,
+    source_name: Option<String>,
+    status: ConnectionStatus,
+    error: Option<AppError>,
+    languages: Vec<LanguageOutcome>,
+    /// Milliseconds between the start of the run and the start of this connection.
+    started_ms: u64,
+    duration_ms: u64,
+}
+
+#[derive(Serialize)]
+pub struct BulkLanguageResult {
+    language_code: String,
+    successful: usize,
+    skipped: usize,
+    app_ids: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct BulkCreateResult {
+    job_id: String,
+    successful: usize,
+    skipped: usize,
+    failed: usize,
+    not_attempted: usize,
+    cancelled: usize,
+    total: usize,
+    app_ids: Vec<String>,
+    skipped_app_ids: Vec<String>,
+    languages: Vec<BulkLanguageResult>,
+    connections: Vec<ConnectionOutcome>,
+    /// Set by the caller from the header image pipeline that ran before the job.
+    pub image_reports: Vec<ImageReport>,
+}
+
+/// Submits the template of a bulk run to one app_id.
+pub trait Submitter {
+    fn submit<'a>(
+        &'a self,
+        app_id: &'a str,
+        progress: &'a ConnectionProgress<'a>,
+    ) -> impl Future<Output = Result<Vec<LanguageOutcome>, AppError>> + Send + 'a;
+}
+
+/// What a bulk run submits and how many connections it works on at a time.
+pub struct JobSettings {
+    pub template_name: String,
+    pub concurrency: usize,
+    pub continue_on_error: bool,
+}
+
+/// State shared by every connection of one bulk run.
+pub struct BulkRun {
+    job_id: String,
+    settings: JobSettings,
+    total: usize,
+    started: Instant,
+    stop: AtomicBool,
+    cancel: CancelToken,
+    reporter: ProgressReporter,
+    journal: JobWriter,
+}
+
+impl BulkRun {
+    pub fn new(
+        app: AppHandle,
+        job_id: &str,
+        settings: JobSettings,
+        total: usize,
+        cancel: CancelToken,
+        journal: JobWriter,
+    ) -> Self {
+        BulkRun {
+            job_id: job_id.to_string(),
+            settings,
+            total,
+            started: Instant::now(),
+            stop: AtomicBool::new(false),
+            cancel,
+            reporter: ProgressReporter::new(app, job_id, total),
+            journal,
+        }
+    }
+
+    /// Submits the template to every target with `submitter` and returns the report. The
+    /// caller registers the job and removes it from the registry afterwards.
+    pub async fn run(self, targets: &[JobTarget], tracker: &StatusTracker, submitter: &(impl Submitter + Sync)) -> BulkCreateResult {
+        let concurrency = self.settings.concurrency;
+        println!("Submitting to up to {} connections at a time (continue on error: {})",
+            concurrency, self.settings.continue_on_error);
+
+        // `buffered` yields the outcomes in connection order whatever order they finish in.
+        let submissions: Vec<_> = targets.iter()
+            .enumerate()
+            .map(|(index, target)| self.submit_connection(target, index + 1, submitter))
+            .collect();
+        let mut submissions = stream::iter(submissions).buffered(concurrency);
+
+        let mut languages: BTreeMap<String, BulkLanguageResult> = BTreeMap::new();
+        let mut connections = Vec::with_capacity(self.total);
+
+        while let Some(outcome) = submissions.next().await {
+            match outcome.status {
+                ConnectionStatus::Created => println!("Template created successfully for app_id: {}", outcome.app_id),
+                ConnectionStatus::SkippedExists => println!("Template already exists for app_id: {}, skipping", outcome.app_id),
+                ConnectionStatus::Failed => {
+                    println!("Template creation failed for app_id {}: {:?}", outcome.app_id, outcome.error);
+                    if !self.settings.continue_on_error {
+                        self.stop.store(true, Ordering::SeqCst);
+                    }
+                }
+                ConnectionStatus::NotAttempted | ConnectionStatus::Cancelled => {}
+            }
+
+            for language in &outcome.languages {
+                let summary = languages.entry(language.language_code.clone())
+                    .or_insert_with(|| BulkLanguageResult {
+                        language_code: language.language_code.clone(),
+                        successful: 0,
+                        skipped: 0,
+                        app_ids: Vec::new(),
+                    });
+                match language.status {
+                    LanguageStatus::Created => {
+                        summary.successful += 1;
+                        summary.app_ids.push(outcome.app_id.clone());
+                        tracker.track(&outcome.app_id, &self.settings.template_name, &language.language_code);
+                    }
+                    LanguageStatus::AlreadyExists => summary.skipped += 1,
+                    LanguageStatus::Failed => {}
+                }
+            }
+
+            connections.push(outcome);
+        }
+        self.journal.finish().await;
+
+        let app_ids_with = |status: ConnectionStatus| -> Vec<String> {
+            connections.iter()
+                .filter(|outcome| outcome.status == status)
+                .map(|outcome| outcome.app_id.clone())
+                .collect()
+        };
+        let app_ids = app_ids_with(ConnectionStatus::Created);
+        let skipped_app_ids = app_ids_with(ConnectionStatus::SkippedExists);
+        let count = |status: ConnectionStatus| connections.iter().filter(|outcome| outcome.status == status).count();
+        let failed = count(ConnectionStatus::Failed);
+        let not_attempted = count(ConnectionStatus::NotAttempted);
+        let cancelled = count(ConnectionStatus::Cancelled);
+
+        println!("Bulk job {} completed: {}/{} successful, {} skipped (already exist), {} failed, {} not attempted, {} cancelled",
+            self.job_id, app_ids.len(), self.total, skipped_app_ids.len(), failed, not_attempted, cancelled);
+
+        BulkCreateResult {
+            job_id: self.job_id.clone(),
+            successful: app_ids.len(),
+            skipped: skipped_app_ids.len(),
+            failed,
+            not_attempted,
+            cancelled,
+            total: self.total,
+            app_ids,
+            skipped_app_ids,
+            languages: languages.into_values().collect(),
+            connections,
+            image_reports: Vec::new(),
+        }
+    }
+
+    /// Submits the template to one connection. Connections reached after the job is
+    /// cancelled are reported as cancelled, and those reached after `stop` is set as
+    /// not attempted.
+    async fn submit_connection(&self, target: &JobTarget, position: usize, submitter: &(impl Submitter + Sync)) -> ConnectionOutcome {
+        let app_id = target.app_id.clone();
+        let progress = self.reporter.connection(&app_id, target.source_name.as_deref());
+        let started = Instant::now();
+        let started_ms = started.duration_since(self.started).as_millis() as u64;
+
+        let skipped_status = if self.cancel.is_cancelled() {
+            Some(ConnectionStatus::Cancelled)
+        } else if self.stop.load(Ordering::SeqCst) {
+            Some(ConnectionStatus::NotAttempted)
+        } else {
+            None
+        };
+        if let Some(status) = skipped_status {
+            println!("[{}/{}] Not attempting app_id {}: {:?}", position, self.total, app_id, status);
+            progress.finished(status, None);
+            self.journal.record(&app_id, status, None).await;
+            return ConnectionOutcome {
+                app_id,
+                source_name: target.source_name.clone(),
+                status,
+                error: None,
+                languages: Vec::new(),
+                started_ms,
+                duration_ms: 0,
+            };
+        }
+
+        println!("[{}/{}] Processing app_id: {}", position, self.total, app_id);
+        progress.started();
+        let (status, error, languages) = match submitter.submit(&app_id, &progress).await {
+            Err(e) => (ConnectionStatus::Failed, Some(e), Vec::new()),
+            Ok(outcomes) => {
+                // Existing templates were already skipped and transient failures retried, so
+                // anything still failed fails the connection.
+                let failed = outcomes.iter().find_map(|outcome| match outcome.status {
+                    LanguageStatus::Failed => outcome.error.clone(),
+                    _ => None,
+                });
+                let status = if failed.is_some() {
+                    ConnectionStatus::Failed
+                } else if outcomes.iter().all(|outcome| outcome.status == LanguageStatus::AlreadyExists) {
+                    ConnectionStatus::SkippedExists
+                } else {
+                    ConnectionStatus::Created
+                };
+                (status, failed, outcomes)
+            }
+        };
+        progress.finished(status, error.as_ref());
+        self.journal.record(&app_id, status, error.as_ref().map(|e| e.message.clone())).await;
+
+        ConnectionOutcome {
+            app_id,
+            source_name: target.source_name.clone(),
+            status,
+            error,
+            languages,
+            started_ms,
+            duration_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
@@ -1,13 +1,37 @@
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::sync::Arc;
+use tauri::Manager;
 
 mod config;
 mod database;
 mod api;
+mod status;
+mod error;
+mod jobs;
+mod storage;
 
 use crate::config::config::load;
 use database::connect;
 use database::fetch;
-use api::gupshup::{TemplateCategory, TemplateRequest, TemplateType, GupshupClient};
+use database::filter::ConnectionFilter;
+use api::authentication::AuthenticationOptions;
+use api::buttons::{validate_buttons, TemplateButton};
+use api::carousel::{self, validate_cards, CardHeaderType, CardParams, CarouselCard};
+use api::limited_offer::LimitedTimeOffer;
+use api::media::{self, MediaKind};
+use api::media_cache::{MediaCache, MEDIA_CACHE_FILE};
+use api::validation::{self, Violation};
+use api::variables::{self, TextPart, VariableError};
+use api::gupshup::{validate_footer, validate_language_code, DEFAULT_LANGUAGE_CODE, TemplateCategory, TemplateRecord, TemplateRequest, TemplateType, GupshupClient};
+use api::header_image::{inspect_header_image, process_header_image, ImageReport};
+use status::tracker::{StatusTracker, TrackedTemplate};
+use error::app_error::{AppError, ErrorCode, FailureAction};
+use jobs::progress::ConnectionProgress;
+use jobs::journal::{JobRecord, JobSummary, JobTarget, JobWriter, Journal};
+use jobs::registry::JobRegistry;
+use jobs::runner::{BulkCreateResult, BulkRun, JobSettings, Submitter, LanguageOutcome, LanguageStatus, DEFAULT_BULK_CONCURRENCY, MAX_BULK_CONCURRENCY};
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -18,11 +42,29 @@ fn greet(name: &str) -> String {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .manage(StatusTracker::default())
+        .manage(JobRegistry::default())
+        .setup(|app| {
+            let media_cache_path = app.path().app_data_dir()?.join(MEDIA_CACHE_FILE);
+            app.manage(Arc::new(MediaCache::load(media_cache_path)));
+            status::tracker::spawn_poller(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet, 
             fetch_all_connections_data, 
             create_template,
-            create_template_for_all_connections
+            list_templates,
+            delete_template,
+            delete_template_for_all_connections,
+            list_tracked_templates,
+            validate_template,
+            create_template_for_all_connections,
+            cancel_job,
+            list_jobs,
+            resume_job,
+            delete_job,
+            preview_connections
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -50,9 +92,9 @@ struct ConnectionsCompleteResult {
 }
 
 #[tauri::command]
-async fn fetch_all_connections_data(params: Params) -> Result<Vec<ConnectionsCompleteResult>, String> {
+async fn fetch_all_connections_data(params: Params) -> Result<Vec<ConnectionsCompleteResult>, AppError> {
     println!("Starting fetch_all_connections_data");
-    let mut env = load();
+    let mut env = load()?;
     if let Some(db_url) = params.db_url {
         if !db_url.is_empty() {
             env.db_url = db_url;
@@ -64,7 +106,7 @@ async fn fetch_all_connections_data(params: Params) -> Result<Vec<ConnectionsCom
         .await
         .map_err(|e| {
             println!("Database connection error: {}", e);
-            format!("Failed to connect to DB: {}", e)
+            AppError::database(format!("Failed to connect to DB: {}", e.message))
         })?;
 
     println!("Connection successful, fetching data...");
@@ -72,7 +114,7 @@ async fn fetch_all_connections_data(params: Params) -> Result<Vec<ConnectionsCom
         .await
         .map_err(|e| {
             println!("Error fetching connections: {}", e);
-            format!("Failed to fetch connections: {}", e)
+            AppError::database(format!("Failed to fetch connections: {}", e.message))
         })?;
 
     println!("Fetched {} connections", data.len());
@@ -96,114 +138,715 @@ async fn fetch_all_connections_data(params: Params) -> Result<Vec<ConnectionsCom
     Ok(results)
 }
 
-#[derive(Serialize, Deserialize)]
-struct CreateTemplateParams {
+#[derive(Serialize, Deserialize, Clone)]
+struct LanguageVariant {
+    language_code: String,
+    content: String,
+    examples: Option<Vec<String>>,
+    header_text: Option<String>,
+    header_examples: Option<Vec<String>>,
+}
+
+impl LanguageVariant {
+    fn header_text(&self) -> Option<&str> {
+        self.header_text.as_deref().filter(|text| !text.is_empty())
+    }
+
+    fn examples(&self) -> &[String] {
+        self.examples.as_deref().unwrap_or_default()
+    }
+
+    fn header_examples(&self) -> &[String] {
+        self.header_examples.as_deref().unwrap_or_default()
+    }
+
+    /// A variable error of this language, with the language code in the message and details.
+    fn variable_error(&self, e: VariableError) -> AppError {
+        AppError {
+            message: format!("{} ({})", e, self.language_code),
+            ..AppError::from(e)
+        }
+        .with_detail("language_code", self.language_code.as_str())
+    }
+
+    /// Checks the variables of the body and header against the given example values.
+    fn check_variables(&self, is_authentication: bool) -> Result<(), AppError> {
+        // Authentication bodies get a sample code when no example is given.
+        if !(is_authentication && self.examples().is_empty()) {
+            variables::fill_examples(&self.content, TextPart::Body, self.examples())
+                .map_err(|e| self.variable_error(e))?;
+        }
+        if let Some(header_text) = self.header_text() {
+            variables::fill_examples(header_text, TextPart::Header, self.header_examples())
+                .map_err(|e| self.variable_error(e))?;
+        }
+        Ok(())
+    }
+}
+
+/// Where the header media is kept among the media `TemplateParams::take_media` returns.
+const HEADER_MEDIA_SLOT: &str = "header";
+
+#[derive(Serialize, Deserialize, Clone)]
+struct TemplateParams {
     template_name: String,
-    app_id: String,
     content: String,
     category: String,
     template_type: String,
     vertical: String,
-    media_id: Option<String>,
-    media_url: Option<String>,
+    language_code: Option<String>,
+    variants: Option<Vec<LanguageVariant>>,
+    examples: Option<Vec<String>>,
     header_text: Option<String>,
+    header_examples: Option<Vec<String>>,
     image_data: Option<Vec<u8>>,
     image_name: Option<String>,
+    buttons: Option<Vec<TemplateButton>>,
+    footer: Option<String>,
+    authentication: Option<AuthenticationOptions>,
+    cards: Option<Vec<CardParams>>,
+    limited_time_offer: Option<LimitedTimeOffer>,
+}
+
+impl TemplateParams {
+    fn footer(&self) -> Option<&str> {
+        self.footer.as_deref().filter(|footer| !footer.trim().is_empty())
+    }
+
+    /// The language versions to submit, or the top-level content as a single variant.
+    fn language_variants(&self) -> Vec<LanguageVariant> {
+        match self.variants {
+            Some(ref variants) if !variants.is_empty() => variants.iter().map(|variant| LanguageVariant {
+                header_text: variant.header_text.clone().or_else(|| self.header_text.clone()),
+                header_examples: variant.header_examples.clone().or_else(|| self.header_examples.clone()),
+                ..variant.clone()
+            }).collect(),
+            _ => vec![LanguageVariant {
+                language_code: self.language_code.clone().unwrap_or(DEFAULT_LANGUAGE_CODE.to_string()),
+                content: self.content.clone(),
+                examples: self.examples.clone(),
+                header_text: self.header_text.clone(),
+                header_examples: self.header_examples.clone(),
+            }],
+        }
+    }
+
+    /// The file name of the header media, defaulting to the one of its kind.
+    fn header_media_name(&self, kind: MediaKind) -> String {
+        self.image_name.clone().unwrap_or_else(|| kind.default_file_name().to_string())
+    }
+
+    /// Runs `process_images` on the blocking thread pool, since decoding, resizing and
+    /// encoding large images would otherwise hold up the async runtime.
+    async fn preprocess_images(&mut self) -> Result<Vec<ImageReport>, AppError> {
+        // Only the media is moved into the task, the rest of the params is cheap to copy.
+        let media = self.take_media();
+        let mut template = self.clone();
+        template.restore_media(media);
+
+        let (template, reports) = tauri::async_runtime::spawn_blocking(move || {
+            let reports = template.process_images();
+            (template, reports)
+        })
+        .await
+        .map_err(|e| AppError::new(ErrorCode::MediaRejected, format!("Image processing failed: {}", e)))?;
+        *self = template;
+        reports
+    }
+
+    /// Runs the image header and image carousel cards through the header image pipeline,
+    /// replacing them with the processed JPEGs before anything is uploaded.
+    fn process_images(&mut self) -> Result<Vec<ImageReport>, AppError> {
+        let rejected = |message: String| AppError::new(ErrorCode::MediaRejected, message);
+        let mut reports = Vec::new();
+
+        if matches!(self.template_type.parse::<TemplateType>(), Ok(TemplateType::Image)) {
+            if let Some(ref image_data) = self.image_data {
+                let processed = process_header_image(image_data, &self.header_media_name(MediaKind::Image)).map_err(rejected)?;
+                self.image_data = Some(processed.data);
+                self.image_name = Some(processed.file_name);
+                reports.push(processed.report);
+            }
+        }
+
+        for card in self.cards.iter_mut().flatten() {
+            if card.header_type == CardHeaderType::Image {
+                let processed = process_header_image(&card.media_data, &card.media_name()).map_err(rejected)?;
+                card.media_data = processed.data;
+                card.media_name = Some(processed.file_name);
+                reports.push(processed.report);
+            }
+        }
+
+        Ok(reports)
+    }
+
+    /// Checks the images `process_images` would convert from their headers alone, which is
+    /// cheap enough to run each time the template is validated.
+    fn inspect_images(&self) -> Result<(), AppError> {
+        let rejected = |message: String| AppError::new(ErrorCode::MediaRejected, message);
+
+        if matches!(self.template_type.parse::<TemplateType>(), Ok(TemplateType::Image)) {
+            if let Some(ref image_data) = self.image_data {
+                inspect_header_image(image_data, &self.header_media_name(MediaKind::Image)).map_err(rejected)?;
+            }
+        }
+
+        for card in self.cards.iter().flatten() {
+            if card.header_type == CardHeaderType::Image {
+                inspect_header_image(&card.media_data, &card.media_name()).map_err(rejected)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves the header media and card media out of the params, keyed by where they belong,
+    /// so the job journal can store them apart from the JSON parameters.
+    fn take_media(&mut self) -> BTreeMap<String, Vec<u8>> {
+        let mut media = BTreeMap::new();
+        if let Some(image_data) = self.image_data.take() {
+            media.insert(HEADER_MEDIA_SLOT.to_string(), image_data);
+        }
+        for (index, card) in self.cards.iter_mut().flatten().enumerate() {
+            media.insert(format!("card-{}", index + 1), std::mem::take(&mut card.media_data));
+        }
+        media
+    }
+
+    /// Puts back the media `take_media` moved out.
+    fn restore_media(&mut self, mut media: BTreeMap<String, Vec<u8>>) {
+        if let Some(image_data) = media.remove(HEADER_MEDIA_SLOT) {
+            self.image_data = Some(image_data);
+        }
+        for (index, card) in self.cards.iter_mut().flatten().enumerate() {
+            if let Some(media_data) = media.remove(&format!("card-{}", index + 1)) {
+                card.media_data = media_data;
+            }
+        }
+    }
+
+    fn check(&self, has_media: bool) -> Result<(TemplateCategory, TemplateType), AppError> {
+        let category: TemplateCategory = self.category.parse().map_err(AppError::validation)?;
+        let template_type: TemplateType = self.template_type.parse().map_err(AppError::validation)?;
+
+        let variants = self.language_variants();
+        let mut language_codes = HashSet::new();
+        for variant in &variants {
+            validate_language_code(&variant.language_code).map_err(AppError::validation)?;
+            if !language_codes.insert(variant.language_code.as_str()) {
+                return Err(AppError::validation(format!("Language {} is listed more than once", variant.language_code)));
+            }
+            if variant.content.trim().is_empty() {
+                return Err(AppError::validation(format!("Content for language {} is empty", variant.language_code)));
+            }
+            variant.check_variables(matches!(category, TemplateCategory::Authentication))?;
+
+            let violations = validation::validate_template(&self.draft_request(variant, category.clone(), template_type.clone()));
+            if !violations.is_empty() {
+                let messages: Vec<String> = violations.into_iter().map(|v| v.message).collect();
+                return Err(AppError::validation(format!("{} ({})", messages.join("; "), variant.language_code)));
+            }
+        }
+        let has_header_text = variants.iter().any(|variant| variant.header_text().is_some());
+
+        if let TemplateType::Location = template_type {
+            // The location itself is only sent with each message, so the template carries no header content.
+            if has_header_text {
+                return Err(AppError::validation("LOCATION templates cannot have a header text"));
+            }
+        }
+
+        if let TemplateType::Carousel = template_type {
+            let cards = self.cards.as_ref().ok_or_else(|| AppError::validation("CAROUSEL templates need cards"))?;
+            validate_cards(cards)?;
+            if has_header_text || has_media {
+                return Err(AppError::validation("CAROUSEL templates cannot have a header, each card has its own media"));
+            }
+            if self.buttons.as_ref().is_some_and(|buttons| !buttons.is_empty()) {
+                return Err(AppError::validation("CAROUSEL templates cannot have buttons outside the cards"));
+            }
+        } else if self.cards.is_some() {
+            return Err(AppError::validation("Cards are only allowed on CAROUSEL templates"));
+        }
+
+        // Checked here like the card media, so a bad file fails before the first upload.
+        match MediaKind::for_template_type(&template_type) {
+            Some(kind) => {
+                if !has_media {
+                    return Err(AppError::validation(format!("{} templates need a header file", template_type.as_str())));
+                }
+                // Images are checked by the header image pipeline, which also converts the WebP
+                // and oversized images that would fail here.
+                if let Some(media_data) = self.image_data.as_ref().filter(|_| kind != MediaKind::Image) {
+                    media::validate_media(kind, media_data, &self.header_media_name(kind)).map_err(AppError::validation)?;
+                }
+            }
+            None if has_media => {
+                return Err(AppError::validation(format!("{} templates cannot have a media header", template_type.as_str())));
+            }
+            None => {}
+        }
+
+        if let Some(ref offer) = self.limited_time_offer {
+            if !matches!(category, TemplateCategory::Marketing) {
+                return Err(AppError::validation("Limited-time offers are only allowed on MARKETING templates"));
+            }
+            if !matches!(template_type, TemplateType::Text | TemplateType::Image | TemplateType::Video) || has_header_text {
+                return Err(AppError::validation("Limited-time offers can only have an image or video header"));
+            }
+            let buttons = self.buttons.as_deref().unwrap_or_default();
+            for variant in &variants {
+                offer.validate(&variant.content, buttons, self.footer()).map_err(AppError::validation)?;
+            }
+        }
+
+        if let TemplateCategory::Authentication = category {
+            let authentication = self.authentication.as_ref()
+                .ok_or_else(|| AppError::validation("AUTHENTICATION templates need an OTP button"))?;
+            authentication.validate().map_err(AppError::validation)?;
+            if !matches!(template_type, TemplateType::Text) || has_header_text || has_media {
+                return Err(AppError::validation("AUTHENTICATION templates cannot have a header"));
+            }
+            if self.buttons.as_ref().is_some_and(|buttons| !buttons.is_empty()) {
+                return Err(AppError::validation("AUTHENTICATION templates only take the OTP button"));
+            }
+            if self.footer().is_some() {
+                return Err(AppError::validation("AUTHENTICATION templates cannot have a custom footer"));
+            }
+        } else if self.authentication.is_some() {
+            return Err(AppError::validation("OTP settings are only allowed on AUTHENTICATION templates"));
+        }
+
+        if let Some(ref buttons) = self.buttons {
+            validate_buttons(buttons).map_err(AppError::validation)?;
+        }
+        if let Some(footer) = self.footer() {
+            validate_footer(footer).map_err(AppError::validation)?;
+        }
+
+        Ok((category, template_type))
+    }
+
+    /// Builds the request for one language without examples or media, for the offline checks.
+    fn draft_request(&self, variant: &LanguageVariant, category: TemplateCategory, template_type: TemplateType) -> TemplateRequest {
+        let mut template_request = TemplateRequest::new(
+            &self.template_name,
+            &variant.content,
+            "",
+            category,
+            template_type,
+            &self.vertical,
+        )
+        .with_language_code(&variant.language_code);
+        template_request.header = variant.header_text().map(str::to_string);
+        template_request.footer = self.footer().map(str::to_string);
+        template_request
+    }
+
+    /// Validates the parameters and builds the request Gupshup receives for one app_id and language.
+    fn build_request(&self, app_id: &str, variant: &LanguageVariant, has_media: bool) -> Result<TemplateRequest, AppError> {
+        let (category, template_type) = self.check(has_media)?;
+
+        let template_request = TemplateRequest::new(
+            &self.template_name,
+            &variant.content,
+            app_id,
+            category,
+            template_type,
+            &self.vertical,
+        )
+        .with_language_code(&variant.language_code);
+        
+        let template_request = if let Some(header_text) = variant.header_text() {
+            template_request.with_header(header_text, variant.header_examples())
+                .map_err(|e| variant.variable_error(e))?
+        } else {
+            template_request
+        };
+        
+        let template_request = template_request.with_buttons(self.buttons.clone().unwrap_or_default());
+        
+        let template_request = if let Some(footer) = self.footer() {
+            template_request.with_footer(footer)
+        } else {
+            template_request
+        };
+
+        let template_request = if let Some(ref offer) = self.limited_time_offer {
+            template_request.with_limited_time_offer(offer.clone())
+        } else {
+            template_request
+        };
+
+        let template_request = if let Some(ref authentication) = self.authentication {
+            template_request.with_authentication(authentication.clone())
+        } else {
+            template_request
+        };
+
+        let template_request = if self.authentication.is_some() && variant.examples().is_empty() {
+            template_request
+        } else {
+            template_request.with_body_examples(variant.examples())
+                .map_err(|e| variant.variable_error(e))?
+        };
+
+        Ok(template_request)
+    }
+}
+
+#[derive(Serialize)]
+struct LanguageViolations {
+    language_code: String,
+    violations: Vec<Violation>,
+}
+
+#[derive(Serialize)]
+struct ValidationReport {
+    valid: bool,
+    error: Option<AppError>,
+    languages: Vec<LanguageViolations>,
 }
 
 #[tauri::command]
-async fn create_template(params: CreateTemplateParams) -> Result<String, String> {
-    println!("Starting create_template for app_id: {}", params.app_id);
-    let env = load();
-    
-    let category = match params.category.as_str() {
-        "MARKETING" => TemplateCategory::Marketing,
-        "UTILITY" => TemplateCategory::Utility,
-        _ => return Err("Invalid category. Must be 'MARKETING' or 'UTILITY'".to_string()),
+fn validate_template(params: TemplateParams) -> ValidationReport {
+    // The header image pipeline only runs on submit, here the images are checked from their headers.
+    let images = params.inspect_images();
+
+    let category = params.category.parse().unwrap_or(TemplateCategory::Marketing);
+    let template_type = params.template_type.parse().unwrap_or(TemplateType::Text);
+
+    let languages: Vec<LanguageViolations> = params.language_variants().iter()
+        .map(|variant| LanguageViolations {
+            language_code: variant.language_code.clone(),
+            violations: validation::validate_template(
+                &params.draft_request(variant, category.clone(), template_type.clone())
+            ),
+        })
+        .collect();
+
+    let has_violations = languages.iter().any(|language| !language.violations.is_empty());
+    // Violations already explain the failure, so only report the remaining parameter errors.
+    let error = if has_violations {
+        None
+    } else {
+        images.err()
+            .or_else(|| params.check(params.image_data.is_some()).err())
     };
-    
-    let template_type = match params.template_type.as_str() {
-        "TEXT" => TemplateType::Text,
-        "IMAGE" => TemplateType::Image,
-        _ => return Err("Invalid template type. Must be 'TEXT' or 'IMAGE'".to_string()),
+
+    ValidationReport {
+        valid: !has_violations && error.is_none(),
+        error,
+        languages,
+    }
+}
+
+/// Uploads the header and carousel card media of the template to the app_id. A header
+/// media handle passed in by the caller is used as is.
+async fn upload_template_media(
+    client: &GupshupClient,
+    params: &TemplateParams,
+    app_id: &str,
+    template_type: &TemplateType,
+    media: Option<(String, String)>,
+) -> Result<(Option<(String, String)>, Vec<CarouselCard>), AppError> {
+    let media = match (media, &params.image_data) {
+        (Some(media), _) => Some(media),
+        (None, Some(image_data)) => {
+            println!("Template has media, media size: {} bytes", image_data.len());
+            let details = client.upload_header_media(app_id, template_type, image_data, params.image_name.clone()).await?;
+            Some((details.id, details.url))
+        }
+        (None, None) => None,
     };
+
+    let cards = match params.cards {
+        Some(ref cards) => client.upload_carousel_cards(app_id, cards).await?,
+        None => Vec::new(),
+    };
+
+    Ok((media, cards))
+}
+
+/// Submits every language variant of the template to one app_id, uploading the header and card media once.
+async fn submit_language_variants(
+    client: &GupshupClient,
+    params: &TemplateParams,
+    app_id: &str,
+    media: Option<(String, String)>,
+    progress: Option<&ConnectionProgress<'_>>,
+) -> Result<Vec<LanguageOutcome>, AppError> {
+    let has_media = media.is_some() || params.image_data.is_some();
+    let (_, template_type) = params.check(has_media)
+        .map_err(|e| e.with_app_id(app_id))?;
+
+    let uploads_media = (media.is_none() && params.image_data.is_some()) || params.cards.is_some();
+    if let Some(progress) = progress {
+        if uploads_media {
+            progress.uploading_media();
+        }
+    }
+
+    let given_media = media.clone();
+    let (mut media, mut cards) = upload_template_media(client, params, app_id, &template_type, media).await?;
+    let mut media_refreshed = false;
+
+    let mut outcomes = Vec::new();
+    for variant in params.language_variants() {
+        println!("Creating template '{}' ({}) of type {} for app_id {}", 
+            params.template_name, variant.language_code, params.template_type, app_id);
+        if let Some(progress) = progress {
+            progress.submitting(&variant.language_code);
+        }
+
+        let result = loop {
+            let template_request = params.build_request(app_id, &variant, has_media)
+                .map_err(|e| e.with_app_id(app_id))?;
+            let template_request = if let Some((ref media_id, ref media_url)) = media {
+                template_request.with_media(media_id, media_url)
+            } else {
+                template_request
+            };
+            let template_request = template_request.with_cards(cards.clone());
+
+            match client.create_template(app_id, template_request).await {
+                // The handles may come from the media cache and have expired on Gupshup's side.
+                Err(e) if uploads_media && !media_refreshed && e.rejects_media() => {
+                    println!("Gupshup rejected the media for app_id {}, uploading it again: {}", app_id, e);
+                    client.forget_media(app_id).await;
+                    (media, cards) = upload_template_media(client, params, app_id, &template_type, given_media.clone()).await?;
+                    media_refreshed = true;
+                }
+                result => break result,
+            }
+        };
+
+        let (status, error, attempts) = match result {
+            Ok(attempts) => (LanguageStatus::Created, None, attempts),
+            Err(e) => {
+                let attempts = e.attempts.unwrap_or(1);
+                if e.code.action() == FailureAction::Skip {
+                    (LanguageStatus::AlreadyExists, Some(e), attempts)
+                } else {
+                    (LanguageStatus::Failed, Some(e), attempts)
+                }
+            }
+        };
+
+        println!("Template creation result for {}: {:?}", variant.language_code, error);
+        outcomes.push(LanguageOutcome {
+            language_code: variant.language_code,
+            status,
+            error,
+            attempts,
+        });
+    }
+
+    Ok(outcomes)
+}
+
+#[derive(Serialize, Deserialize)]
+struct CreateTemplateParams {
+    app_id: String,
+    media_id: Option<String>,
+    media_url: Option<String>,
+    #[serde(flatten)]
+    template: TemplateParams,
+    /// Build and validate the requests without uploading media or creating anything.
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Stands in for the media id and url of uploads a dry run does not perform.
+const DRY_RUN_MEDIA_PLACEHOLDER: &str = "<uploaded on submit>";
+
+#[derive(Serialize)]
+struct DryRunPayload {
+    app_id: String,
+    language_code: String,
+    request: TemplateRequest,
+    form: BTreeMap<&'static str, String>,
+}
+
+#[derive(Serialize)]
+struct DryRunResult {
+    dry_run: bool,
+    targets: Vec<JobTarget>,
+    /// The media every target would upload before creating the template.
+    media_uploads: Vec<PlannedUpload>,
+    payloads: Vec<DryRunPayload>,
+    /// What the header image pipeline changed in the images that would be uploaded.
+    image_reports: Vec<ImageReport>,
+}
+
+/// A media file a dry run would upload, as detected by the checks the upload runs.
+#[derive(Serialize)]
+struct PlannedUpload {
+    file_name: String,
+    kind: MediaKind,
+    mime: &'static str,
+    size: usize,
+}
+
+/// Runs the checks `GupshupClient::upload_media` runs on every file the template would
+/// upload, so a file the upload would reject fails the dry run too.
+fn planned_media_uploads(params: &TemplateParams, media: Option<&(String, String)>) -> Result<Vec<PlannedUpload>, AppError> {
+    let template_type: TemplateType = params.template_type.parse().map_err(AppError::validation)?;
+    let mut files = Vec::new();
+    if let (None, Some(image_data), Some(kind)) = (media, &params.image_data, MediaKind::for_template_type(&template_type)) {
+        files.push((kind, image_data.as_slice(), params.header_media_name(kind)));
+    }
+    for card in params.cards.iter().flatten() {
+        files.push((card.header_type.media_kind(), card.media_data.as_slice(), card.media_name()));
+    }
+
+    files.into_iter()
+        .map(|(kind, data, file_name)| {
+            let mime = media::validate_media(kind, data, &file_name)
+                .map_err(|e| AppError::new(ErrorCode::MediaRejected, e))?;
+            Ok(PlannedUpload { file_name, kind, mime, size: data.len() })
+        })
+        .collect()
+}
+
+/// Builds the requests `submit_language_variants` would send to the app_id.
+fn dry_run_payloads(
+    params: &TemplateParams,
+    app_id: &str,
+    media: Option<&(String, String)>,
+) -> Result<Vec<DryRunPayload>, AppError> {
+    let has_media = media.is_some() || params.image_data.is_some();
+    let (_, template_type) = params.check(has_media)
+        .map_err(|e| e.with_app_id(app_id))?;
+    let media_header = MediaKind::for_template_type(&template_type).is_some();
+
+    let (media_id, media_url) = match media {
+        Some((media_id, media_url)) => (media_id.as_str(), media_url.as_str()),
+        None => (DRY_RUN_MEDIA_PLACEHOLDER, DRY_RUN_MEDIA_PLACEHOLDER),
+    };
+    let cards = params.cards.iter().flatten()
+        .enumerate()
+        .map(|(index, card)| CarouselCard::new(card, DRY_RUN_MEDIA_PLACEHOLDER, DRY_RUN_MEDIA_PLACEHOLDER)
+            .map_err(|e| carousel::card_variable_error(index + 1, e).with_app_id(app_id)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut payloads = Vec::new();
+    for variant in params.language_variants() {
+        let request = params.build_request(app_id, &variant, has_media)
+            .map_err(|e| e.with_app_id(app_id))?;
+        let request = if media_header {
+            request.with_media(media_id, media_url)
+        } else {
+            request
+        };
+        let request = request.with_cards(cards.clone());
+
+        payloads.push(DryRunPayload {
+            app_id: app_id.to_string(),
+            language_code: variant.language_code,
+            form: request.form_fields().into_iter().collect(),
+            request,
+        });
+    }
+
+    Ok(payloads)
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum CreateTemplateResponse {
+    Submitted(Vec<LanguageOutcome>),
+    DryRun(DryRunResult),
+}
+
+#[tauri::command]
+async fn create_template(
+    params: CreateTemplateParams,
+    tracker: tauri::State<'_, StatusTracker>,
+    media_cache: tauri::State<'_, Arc<MediaCache>>,
+) -> Result<CreateTemplateResponse, AppError> {
+    println!("Starting create_template for app_id: {} (dry run: {})", params.app_id, params.dry_run);
     
-    let client = GupshupClient::new(&env.apikey, &env.cookie);
-    
-    let template_request = TemplateRequest::new(
-        &params.template_name,
-        &params.content,
-        &params.app_id,
-        category,
-        template_type,
-        &params.vertical,
-    );
+    let mut params = params;
+    let image_reports = params.template.preprocess_images().await?;
+    for report in &image_reports {
+        println!("Prepared header image {}: {:?}", report.file_name, report.changes);
+    }
     
-    let template_request = if let Some(header_text) = params.header_text {
-        template_request.with_header_example(&header_text)
-    } else {
-        template_request
+    let media = match (params.media_id, params.media_url, &params.template.image_data) {
+        (Some(media_id), Some(media_url), None) => Some((media_id, media_url)),
+        _ => None,
     };
     
-    let template_request = if let (Some(media_id), Some(media_url), None) = (&params.media_id, &params.media_url, &params.image_data) {
-        template_request.with_media(media_id, media_url)
-    } else {
-        template_request
-    };
+    if params.dry_run {
+        let payloads = dry_run_payloads(&params.template, &params.app_id, media.as_ref())?;
+        return Ok(CreateTemplateResponse::DryRun(DryRunResult {
+            dry_run: true,
+            targets: vec![JobTarget { app_id: params.app_id.clone(), source_name: None }],
+            media_uploads: planned_media_uploads(&params.template, media.as_ref())
+                .map_err(|e| e.with_app_id(&params.app_id))?,
+            payloads,
+            image_reports,
+        }));
+    }
     
-    println!("Creating template '{}' of type {} for app_id {}", 
-        params.template_name, params.template_type, params.app_id);
+    let env = load()?;
+    let client = GupshupClient::new(&env.apikey, &env.cookie)
+        .with_retry_policy(env.retry)
+        .with_media_cache(media_cache.inner().clone());
     
-    let result = if let Some(image_data) = params.image_data {
-        println!("Template has image, image size: {} bytes", image_data.len());
-        client.create_template_with_image(&params.app_id, template_request, Some(image_data), params.image_name)
-            .await?
-    } else {
-        println!("Creating text-only template");
-        client.create_template(&params.app_id, template_request)
-            .await?
-    };
+    let outcomes = submit_language_variants(&client, &params.template, &params.app_id, media, None).await?;
     
-    println!("Template creation result: {}", result.status);
+    for outcome in &outcomes {
+        if outcome.status == LanguageStatus::Created {
+            tracker.track(&params.app_id, &params.template.template_name, &outcome.language_code);
+        }
+    }
     
-    match result.status.as_str() {
-        "success" => Ok("Template created successfully".to_string()),
-        _ => Err(result.message.unwrap_or("Unknown error".to_string())),
+    if !outcomes.iter().any(|outcome| outcome.status == LanguageStatus::Created) {
+        // Nothing was created, so report the first failure, or that the template already exists.
+        let error = outcomes.iter()
+            .find(|outcome| outcome.status == LanguageStatus::Failed)
+            .or(outcomes.first())
+            .and_then(|outcome| outcome.error.clone());
+        if let Some(error) = error {
+            return Err(error);
+        }
     }
+    
+    Ok(CreateTemplateResponse::Submitted(outcomes))
 }
 
-#[derive(Serialize, Deserialize)]
-struct BulkCreateTemplateParams {
-    template_name: String,
-    content: String,
-    category: String,
-    template_type: String,
-    vertical: String,
-    header_text: Option<String>,
-    image_data: Option<Vec<u8>>,
-    image_name: Option<String>,
+#[tauri::command]
+fn list_tracked_templates(tracker: tauri::State<'_, StatusTracker>) -> Vec<TrackedTemplate> {
+    tracker.snapshot()
 }
 
-#[derive(Serialize)]
-struct BulkCreateResult {
-    successful: usize,
-    total: usize,
-    app_ids: Vec<String>,
+#[derive(Serialize, Deserialize)]
+struct ListTemplatesParams {
+    app_id: String,
 }
 
 #[tauri::command]
-async fn create_template_for_all_connections(
-    params: BulkCreateTemplateParams
-) -> Result<BulkCreateResult, String> {
-    println!("Starting create_template_for_all_connections");
-    let env = load();
-    
+async fn list_templates(params: ListTemplatesParams) -> Result<Vec<TemplateRecord>, AppError> {
+    println!("Starting list_templates for app_id: {}", params.app_id);
+    let env = load()?;
+
+    let client = GupshupClient::new(&env.apikey, &env.cookie).with_retry_policy(env.retry);
+    let templates = client.list_templates(&params.app_id).await?;
+
+    println!("Returning {} templates for app_id {}", templates.len(), params.app_id);
+    Ok(templates)
+}
+
+async fn fetch_connections_with_app_id(db_url: &str) -> Result<Vec<fetch::ConnectionData>, AppError> {
     println!("Connecting to database to retrieve connections");
-    let mut db_conn = connect::connect_db(&env.db_url)
+    let mut db_conn = connect::connect_db(db_url)
         .await
         .map_err(|e| {
             println!("Database connection error: {}", e);
-            format!("Failed to connect to DB: {}", e)
+            AppError::database(format!("Failed to connect to DB: {}", e.message))
         })?;
     
     println!("Fetching connections from database");
@@ -211,106 +854,418 @@ async fn create_template_for_all_connections(
         .await
         .map_err(|e| {
             println!("Error fetching connections: {}", e);
-            format!("Failed to fetch connections: {}", e)
+            AppError::database(format!("Failed to fetch connections: {}", e.message))
         })?;
     
     println!("Found {} total connections", connections.len());
     let connections_with_app_id: Vec<_> = connections.into_iter()
-        .filter(|conn| conn.app_id.is_some() && !conn.app_id.as_ref().unwrap().is_empty())
+        .filter(|conn| conn.app_id.as_ref().is_some_and(|app_id| !app_id.is_empty()))
         .collect();
     
     println!("Found {} connections with valid app_id", connections_with_app_id.len());
     
     if connections_with_app_id.is_empty() {
-        return Err("No connections found with valid app_id".to_string());
+        return Err(AppError::database("No connections found with valid app_id"));
+    }
+
+    Ok(connections_with_app_id)
+}
+
+/// Fetches the connections with an app_id that match the filter.
+async fn fetch_filtered_connections(db_url: &str, filter: &ConnectionFilter) -> Result<Vec<fetch::ConnectionData>, AppError> {
+    let connections = filter.apply(fetch_connections_with_app_id(db_url).await?);
+    println!("{} connections match the filter", connections.len());
+
+    if connections.is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidParameter, "No connections match the filter"));
+    }
+
+    Ok(connections)
+}
+
+#[derive(Serialize)]
+struct ConnectionPreview {
+    id: i32,
+    source_name: Option<String>,
+    app_id: Option<String>,
+    qualidade: Option<String>,
+    restriction: Option<String>,
+    issues: i32,
+    msg_limit: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ConnectionPreviewResult {
+    total: usize,
+    matched: usize,
+    connections: Vec<ConnectionPreview>,
+}
+
+/// Lists the connections a bulk run with this filter would target.
+#[tauri::command]
+async fn preview_connections(params: ConnectionFilter) -> Result<ConnectionPreviewResult, AppError> {
+    println!("Starting preview_connections with filter: {:?}", params);
+    let env = load()?;
+
+    let connections = fetch_connections_with_app_id(&env.db_url).await?;
+    let total = connections.len();
+    let connections: Vec<ConnectionPreview> = params.apply(connections).into_iter()
+        .map(|connection| ConnectionPreview {
+            id: connection.id,
+            source_name: connection.source_name,
+            app_id: connection.app_id,
+            qualidade: connection.qualidade,
+            restriction: connection.restriction,
+            issues: connection.issues,
+            msg_limit: connection.msg_limit,
+        })
+        .collect();
+
+    println!("{} of {} connections match the filter", connections.len(), total);
+    Ok(ConnectionPreviewResult {
+        total,
+        matched: connections.len(),
+        connections,
+    })
+}
+
+/// Submits the template of a bulk run with `submit_language_variants`.
+struct TemplateSubmitter<'a> {
+    client: &'a GupshupClient,
+    template: &'a TemplateParams,
+}
+
+impl Submitter for TemplateSubmitter<'_> {
+    fn submit<'a>(
+        &'a self,
+        app_id: &'a str,
+        progress: &'a ConnectionProgress<'a>,
+    ) -> impl Future<Output = Result<Vec<LanguageOutcome>, AppError>> + Send + 'a {
+        submit_language_variants(self.client, self.template, app_id, None, Some(progress))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BulkCreateTemplateParams {
+    #[serde(flatten)]
+    template: TemplateParams,
+    /// How many app_ids are submitted at the same time. Only allowed together with
+    /// `continue_on_error`, where it defaults to `DEFAULT_BULK_CONCURRENCY`.
+    concurrency: Option<usize>,
+    /// Keep submitting to the remaining connections after one fails. Without it the run
+    /// stops at the first failure and submits one app_id at a time, since connections
+    /// already running when a failure arrives could not be stopped.
+    #[serde(default)]
+    continue_on_error: bool,
+    /// Build and validate the requests for every connection without submitting them.
+    #[serde(default)]
+    dry_run: bool,
+    /// Which connections to target, all of them by default.
+    #[serde(default)]
+    filter: ConnectionFilter,
+}
+
+impl BulkCreateTemplateParams {
+    fn job_settings(&self) -> Result<JobSettings, AppError> {
+        let concurrency = match self.concurrency {
+            Some(concurrency) if concurrency > 1 && !self.continue_on_error => return Err(AppError::validation(
+                "Submitting to several connections at a time needs continue_on_error, a run that stops at the first failure submits one at a time"
+            )),
+            Some(concurrency) => concurrency.clamp(1, MAX_BULK_CONCURRENCY),
+            None if self.continue_on_error => DEFAULT_BULK_CONCURRENCY,
+            None => 1,
+        };
+        Ok(JobSettings {
+            template_name: self.template.template_name.clone(),
+            concurrency,
+            continue_on_error: self.continue_on_error,
+        })
+    }
+}
+
+
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BulkCreateResponse {
+    Submitted(BulkCreateResult),
+    DryRun(DryRunResult),
+}
+
+#[tauri::command]
+async fn create_template_for_all_connections(
+    app: tauri::AppHandle,
+    params: BulkCreateTemplateParams,
+    tracker: tauri::State<'_, StatusTracker>,
+    jobs: tauri::State<'_, JobRegistry>,
+    media_cache: tauri::State<'_, Arc<MediaCache>>,
+) -> Result<BulkCreateResponse, AppError> {
+    println!("Starting create_template_for_all_connections");
+    let env = load()?;
+    
+    let mut params = params;
+    let settings = params.job_settings()?;
+    let image_reports = params.template.preprocess_images().await?;
+    
+    params.template.check(params.template.image_data.is_some())?;
+    
+    let connections = fetch_filtered_connections(&env.db_url, &params.filter).await?;
+    let targets: Vec<JobTarget> = connections.into_iter()
+        .map(|connection| JobTarget {
+            app_id: connection.app_id.unwrap_or_default(),
+            source_name: connection.source_name,
+        })
+        .collect();
+    
+    if params.dry_run {
+        println!("Dry run: building requests for {} connections", targets.len());
+        let mut payloads = Vec::new();
+        for target in &targets {
+            payloads.extend(dry_run_payloads(&params.template, &target.app_id, None)?);
+        }
+        return Ok(BulkCreateResponse::DryRun(DryRunResult {
+            dry_run: true,
+            media_uploads: planned_media_uploads(&params.template, None)?,
+            targets,
+            payloads,
+            image_reports,
+        }));
     }
     
-    let category = match params.category.as_str() {
-        "MARKETING" => TemplateCategory::Marketing,
-        "UTILITY" => TemplateCategory::Utility,
-        _ => return Err("Invalid category. Must be 'MARKETING' or 'UTILITY'".to_string()),
-    };
+    let client = GupshupClient::new(&env.apikey, &env.cookie)
+        .with_retry_policy(env.retry)
+        .with_media_cache(media_cache.inner().clone());
+    let total = targets.len();
+    
+    println!("Starting template creation for {} connections", total);
+    
+    let journal = Journal::open(&app).await?;
+    let (job_id, cancel) = jobs.start();
+    println!("Started bulk job {}", job_id);
     
-    let template_type = match params.template_type.as_str() {
-        "TEXT" => TemplateType::Text,
-        "IMAGE" => TemplateType::Image,
-        _ => return Err("Invalid template type. Must be 'TEXT' or 'IMAGE'".to_string()),
+    // The media is stored next to the journal and only referenced from the journaled parameters.
+    let media = params.template.take_media();
+    let record = serde_json::to_value(&params)
+        .map_err(|e| AppError::storage(format!("Failed to serialize job parameters: {}", e)))
+        .map(|value| JobRecord::new(&job_id, &params.template.template_name, value, targets.clone()));
+    let journal = match record {
+        Ok(record) => JobWriter::create(journal, record, &media).await,
+        Err(e) => Err(e),
+    };
+    params.template.restore_media(media);
+    let journal = match journal {
+        Ok(journal) => journal,
+        Err(e) => {
+            jobs.finish(&job_id);
+            return Err(e);
+        }
     };
     
-    let client = GupshupClient::new(&env.apikey, &env.cookie);
+    let run = BulkRun::new(app, &job_id, settings, total, cancel, journal);
+    let mut result = run.run(&targets, &tracker, &TemplateSubmitter { client: &client, template: &params.template }).await;
+    jobs.finish(&job_id);
+    result.image_reports = image_reports;
     
-    let mut successful = 0;
-    let total = connections_with_app_id.len();
-    let mut successful_app_ids = Vec::new();
+    Ok(BulkCreateResponse::Submitted(result))
+}
+
+#[derive(Serialize, Deserialize)]
+struct CancelJobParams {
+    job_id: String,
+}
+
+/// Stops a running bulk job from starting new connections. Requests already in
+/// flight finish and the job returns its partial report.
+#[tauri::command]
+fn cancel_job(params: CancelJobParams, jobs: tauri::State<'_, JobRegistry>) -> Result<(), AppError> {
+    println!("Cancelling bulk job {}", params.job_id);
+    if jobs.cancel(&params.job_id) {
+        Ok(())
+    } else {
+        Err(AppError::new(ErrorCode::InvalidParameter, format!("No running job with id {}", params.job_id)))
+    }
+}
+
+#[tauri::command]
+async fn list_jobs(app: tauri::AppHandle) -> Result<Vec<JobSummary>, AppError> {
+    Journal::open(&app).await?.list().await
+}
+
+#[derive(Serialize, Deserialize)]
+struct DeleteJobParams {
+    job_id: String,
+}
+
+/// Deletes a journaled job that is not running, together with its stored media.
+#[tauri::command]
+async fn delete_job(
+    app: tauri::AppHandle,
+    params: DeleteJobParams,
+    jobs: tauri::State<'_, JobRegistry>,
+) -> Result<(), AppError> {
+    println!("Deleting bulk job {}", params.job_id);
+    if jobs.is_running(&params.job_id) {
+        return Err(AppError::new(ErrorCode::InvalidParameter, format!("Job {} is still running", params.job_id)));
+    }
+    Journal::open(&app).await?.delete(&params.job_id).await
+}
+
+#[derive(Serialize, Deserialize)]
+struct ResumeJobParams {
+    job_id: String,
+}
+
+/// Runs a journaled bulk job again for the connections that did not end with the
+/// template created or already existing.
+#[tauri::command]
+async fn resume_job(
+    app: tauri::AppHandle,
+    params: ResumeJobParams,
+    tracker: tauri::State<'_, StatusTracker>,
+    jobs: tauri::State<'_, JobRegistry>,
+    media_cache: tauri::State<'_, Arc<MediaCache>>,
+) -> Result<BulkCreateResult, AppError> {
+    println!("Resuming bulk job {}", params.job_id);
+    let env = load()?;
     
-    println!("Starting template creation for {} connections", connections_with_app_id.len());
+    let journal = Journal::open(&app).await?;
+    let record = journal.load(&params.job_id).await?;
+    let mut bulk_params: BulkCreateTemplateParams = serde_json::from_value(record.params.clone())
+        .map_err(|e| AppError::storage(format!("Failed to read parameters of job {}: {}", params.job_id, e)))?;
+    bulk_params.template.restore_media(journal.load_media(&record).await?);
+    let settings = bulk_params.job_settings()?;
+    
+    // Journaled images were already processed, so this only repeats the checks.
+    let image_reports = bulk_params.template.preprocess_images().await?;
+    bulk_params.template.check(bulk_params.template.image_data.is_some())?;
+    
+    let targets = record.pending_targets();
+    if targets.is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidParameter, format!("Job {} has no unfinished connections", params.job_id)));
+    }
+    println!("{} of {} connections of job {} are unfinished", targets.len(), record.targets.len(), params.job_id);
+    
+    let cancel = jobs.resume(&params.job_id)
+        .ok_or_else(|| AppError::new(ErrorCode::InvalidParameter, format!("Job {} is still running", params.job_id)))?;
+    let journal = match JobWriter::start(journal, record).await {
+        Ok(journal) => journal,
+        Err(e) => {
+            jobs.finish(&params.job_id);
+            return Err(e);
+        }
+    };
     
-    let mut skipped = 0;
-    let mut skipped_app_ids = Vec::new();
+    let client = GupshupClient::new(&env.apikey, &env.cookie)
+        .with_retry_policy(env.retry)
+        .with_media_cache(media_cache.inner().clone());
+    let run = BulkRun::new(app, &params.job_id, settings, targets.len(), cancel, journal);
+    let mut result = run.run(&targets, &tracker, &TemplateSubmitter { client: &client, template: &bulk_params.template }).await;
+    jobs.finish(&params.job_id);
+    result.image_reports = image_reports;
     
-    for (index, connection) in connections_with_app_id.iter().enumerate() {
-        let app_id = connection.app_id.as_ref().unwrap();
-        println!("[{}/{}] Processing app_id: {}", index + 1, total, app_id);
+    Ok(result)
+}
 
-        let template_request = TemplateRequest::new(
-            &params.template_name,
-            &params.content,
-            &app_id,
-            category.clone(),
-            template_type.clone(),
-            &params.vertical,
-        );
-        
-        let template_request = if let Some(ref header_text) = params.header_text {
-            template_request.with_header_example(header_text)
-        } else {
-            template_request
-        };
-        
-        let result = if let Some(ref image_data) = params.image_data {
-            client.create_template_with_image(
-                &app_id,
-                template_request,
-                Some(image_data.clone()),
-                params.image_name.clone()
-            )
-            .await
-            .map_err(|e| format!("Failed to create template for app_id {}: {}", app_id, e))?
-        } else {
-            client.create_template(&app_id, template_request)
-                .await
-                .map_err(|e| format!("Failed to create template for app_id {}: {}", app_id, e))?
+#[derive(Serialize, Deserialize)]
+struct DeleteTemplateParams {
+    app_id: String,
+    template_name: String,
+}
+
+#[tauri::command]
+async fn delete_template(
+    params: DeleteTemplateParams,
+    tracker: tauri::State<'_, StatusTracker>,
+) -> Result<String, AppError> {
+    println!("Starting delete_template '{}' for app_id: {}", params.template_name, params.app_id);
+    validation::validate_element_name(&params.template_name).map_err(AppError::validation)?;
+    let env = load()?;
+
+    let client = GupshupClient::new(&env.apikey, &env.cookie).with_retry_policy(env.retry);
+    client.delete_template(&params.app_id, &params.template_name).await?;
+    tracker.untrack(&params.app_id, &params.template_name);
+
+    Ok("Template deleted successfully".to_string())
+}
+
+#[derive(Serialize, Deserialize)]
+struct BulkDeleteTemplateParams {
+    template_name: String,
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+enum DeleteStatus {
+    Deleted,
+    NotFound,
+    Failed,
+}
+
+#[derive(Serialize)]
+struct DeleteOutcome {
+    app_id: String,
+    source_name: Option<String>,
+    status: DeleteStatus,
+    error: Option<AppError>,
+}
+
+#[derive(Serialize)]
+struct BulkDeleteResult {
+    deleted: usize,
+    not_found: usize,
+    failed: usize,
+    total: usize,
+    results: Vec<DeleteOutcome>,
+}
+
+#[tauri::command]
+async fn delete_template_for_all_connections(
+    params: BulkDeleteTemplateParams,
+    tracker: tauri::State<'_, StatusTracker>,
+) -> Result<BulkDeleteResult, AppError> {
+    println!("Starting delete_template_for_all_connections for template '{}'", params.template_name);
+    validation::validate_element_name(&params.template_name).map_err(AppError::validation)?;
+    let env = load()?;
+
+    let connections_with_app_id = fetch_connections_with_app_id(&env.db_url).await?;
+    let client = GupshupClient::new(&env.apikey, &env.cookie).with_retry_policy(env.retry);
+    let total = connections_with_app_id.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (index, connection) in connections_with_app_id.into_iter().enumerate() {
+        let app_id = connection.app_id.unwrap_or_default();
+        println!("[{}/{}] Deleting template from app_id: {}", index + 1, total, app_id);
+
+        let (status, error) = match client.delete_template(&app_id, &params.template_name).await {
+            Ok(()) => (DeleteStatus::Deleted, None),
+            Err(e) if e.is(ErrorCode::TemplateNotFound) => (DeleteStatus::NotFound, Some(e)),
+            Err(e) => (DeleteStatus::Failed, Some(e)),
         };
-        
-        if result.status != "success" {
-            // Check if the error is about an existing template
-            let error_message = result.message.unwrap_or("Unknown error".to_string());
-            if error_message.contains("Template Already exists with same namespace and elementName and languageCode") {
-                println!("Template already exists for app_id: {}, skipping", app_id);
-                skipped += 1;
-                skipped_app_ids.push(app_id.clone());
-                continue; // Skip to the next connection
-            }
-            
-            // For other errors, fail the operation
-            return Err(format!(
-                "Failed to create template for app_id {}: {}", 
-                app_id, 
-                error_message
-            ));
+        if status != DeleteStatus::Failed {
+            tracker.untrack(&app_id, &params.template_name);
         }
-        
-        println!("Template created successfully for app_id: {}", app_id);
-        successful += 1;
-        successful_app_ids.push(app_id.clone());
+
+        results.push(DeleteOutcome {
+            app_id,
+            source_name: connection.source_name,
+            status,
+            error,
+        });
     }
-    
-    println!("Bulk template creation completed: {}/{} successful, {} skipped (already exist)", 
-             successful, total, skipped);
-    
-    Ok(BulkCreateResult {
-        successful,
+
+    let count = |status: DeleteStatus| results.iter().filter(|r| r.status == status).count();
+    let deleted = count(DeleteStatus::Deleted);
+    let not_found = count(DeleteStatus::NotFound);
+    let failed = count(DeleteStatus::Failed);
+
+    println!("Bulk template deletion completed: {}/{} deleted, {} not found, {} failed",
+             deleted, total, not_found, failed);
+
+    Ok(BulkDeleteResult {
+        deleted,
+        not_found,
+        failed,
         total,
-        app_ids: successful_app_ids,
+        results,
     })
 }
\ No newline at end of file
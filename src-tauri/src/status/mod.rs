@@ -0,0 +1 @@
+pub mod tracker;
\ No newline at end of file
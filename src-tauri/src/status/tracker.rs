@@ -0,0 +1,183 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::api::gupshup::{GupshupClient, TemplateRecord};
+use crate::config::config::load;
+
+pub const STATUS_EVENT: &str = "template-status";
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+/// How long a template stays listed after reaching a final status.
+const FINAL_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub enum TemplateStatus {
+    #[serde(rename = "PENDING")]
+    Pending,
+    #[serde(rename = "APPROVED")]
+    Approved,
+    #[serde(rename = "REJECTED")]
+    Rejected,
+    #[serde(rename = "PAUSED")]
+    Paused,
+    #[serde(rename = "DISABLED")]
+    Disabled,
+    #[serde(rename = "UNKNOWN")]
+    Unknown,
+}
+
+impl TemplateStatus {
+    pub fn parse(status: &str) -> Self {
+        match status.to_uppercase().as_str() {
+            "PENDING" | "SUBMITTED" => TemplateStatus::Pending,
+            "APPROVED" | "ENABLED" => TemplateStatus::Approved,
+            "REJECTED" | "FAILED" => TemplateStatus::Rejected,
+            "PAUSED" => TemplateStatus::Paused,
+            "DISABLED" => TemplateStatus::Disabled,
+            _ => TemplateStatus::Unknown,
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        !matches!(self, TemplateStatus::Pending | TemplateStatus::Unknown)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TrackedTemplate {
+    pub app_id: String,
+    pub element_name: String,
+    pub language_code: String,
+    pub status: TemplateStatus,
+    pub reason: Option<String>,
+    #[serde(skip)]
+    tracked_at: Instant,
+    #[serde(skip)]
+    finalized_at: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct StatusTracker {
+    tracked: Mutex<HashMap<(String, String, String), TrackedTemplate>>,
+}
+
+impl StatusTracker {
+    pub fn track(&self, app_id: &str, element_name: &str, language_code: &str) {
+        println!("Tracking approval status of template '{}' ({}) for app_id: {}", element_name, language_code, app_id);
+        let mut tracked = self.tracked.lock().unwrap();
+        let key = (app_id.to_string(), element_name.to_string(), language_code.to_string());
+        tracked.insert(key, TrackedTemplate {
+            app_id: app_id.to_string(),
+            element_name: element_name.to_string(),
+            language_code: language_code.to_string(),
+            status: TemplateStatus::Pending,
+            reason: None,
+            tracked_at: Instant::now(),
+            finalized_at: None,
+        });
+    }
+
+    /// Stops tracking every language of a template, used once it is deleted.
+    pub fn untrack(&self, app_id: &str, element_name: &str) {
+        let mut tracked = self.tracked.lock().unwrap();
+        tracked.retain(|(tracked_app_id, tracked_name, _), _| tracked_app_id != app_id || tracked_name != element_name);
+    }
+
+    pub fn snapshot(&self) -> Vec<TrackedTemplate> {
+        self.tracked.lock().unwrap().values().cloned().collect()
+    }
+
+    fn pending_app_ids(&self) -> Vec<String> {
+        let tracked = self.tracked.lock().unwrap();
+        let mut app_ids: Vec<String> = tracked.values()
+            .filter(|t| !t.status.is_final())
+            .map(|t| t.app_id.clone())
+            .collect();
+        app_ids.sort();
+        app_ids.dedup();
+        app_ids
+    }
+
+    /// Stores the latest status of the app_id's templates from a successful listing and
+    /// returns the entries that changed. Templates missing from the listing were deleted
+    /// elsewhere and are dropped, unless they were only just submitted.
+    fn update(&self, app_id: &str, templates: &[TemplateRecord]) -> Vec<TrackedTemplate> {
+        let mut tracked = self.tracked.lock().unwrap();
+        let mut changed = Vec::new();
+
+        tracked.retain(|(tracked_app_id, element_name, language_code), entry| {
+            if tracked_app_id != app_id {
+                return true;
+            }
+            let template = templates.iter()
+                .find(|template| &template.element_name == element_name && &template.language_code == language_code);
+            let Some(template) = template else {
+                return entry.tracked_at.elapsed() < POLL_INTERVAL;
+            };
+
+            let status = TemplateStatus::parse(&template.status);
+            if entry.status != status || entry.reason != template.reason {
+                entry.finalized_at = status.is_final().then(Instant::now);
+                entry.status = status;
+                entry.reason = template.reason.clone();
+                changed.push(entry.clone());
+            }
+            true
+        });
+
+        changed
+    }
+
+    /// Drops templates that reached a final status more than `FINAL_RETENTION` ago.
+    fn prune(&self) {
+        let mut tracked = self.tracked.lock().unwrap();
+        tracked.retain(|_, entry| entry.finalized_at.is_none_or(|finalized_at| finalized_at.elapsed() < FINAL_RETENTION));
+    }
+}
+
+pub fn spawn_poller(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            poll_once(&app).await;
+        }
+    });
+}
+
+async fn poll_once(app: &AppHandle) {
+    let tracker = app.state::<StatusTracker>();
+    tracker.prune();
+    let app_ids = tracker.pending_app_ids();
+    if app_ids.is_empty() {
+        return;
+    }
+
+    println!("Polling template status for {} app_ids", app_ids.len());
+    let env = match load() {
+        Ok(env) => env,
+        Err(e) => {
+            println!("Error loading configuration for status polling: {}", e);
+            return;
+        }
+    };
+    let client = GupshupClient::new(&env.apikey, &env.cookie).with_retry_policy(env.retry);
+
+    for app_id in app_ids {
+        let templates = match client.list_templates(&app_id).await {
+            Ok(templates) => templates,
+            Err(e) => {
+                println!("Error polling template status for app_id {}: {}", app_id, e);
+                continue;
+            }
+        };
+
+        for changed in tracker.update(&app_id, &templates) {
+            println!("Template '{}' for app_id {} is now {:?}", changed.element_name, changed.app_id, changed.status);
+            if let Err(e) = app.emit(STATUS_EVENT, &changed) {
+                println!("Error emitting template status event: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs;
+
+use crate::error::app_error::AppError;
+
+/// Milliseconds since the Unix epoch, the timestamp format of the media cache and the job journal.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Hex encoded SHA-256 of the content, used to key cached media and name journaled media.
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Writes to a temporary file first so a crash never leaves a half written file behind.
+pub async fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), AppError> {
+    let mut temp_name = path.as_os_str().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let result = match fs::write(&temp_path, contents).await {
+        Ok(()) => fs::rename(&temp_path, path).await,
+        Err(e) => Err(e),
+    };
+    result.map_err(|e| AppError::storage(format!("Failed to write {}: {}", path.display(), e)))
+}
@@ -0,0 +1 @@
+pub mod files;
\ No newline at end of file
@@ -6,12 +6,108 @@ type TemplateType = 'marketing' | 'utility';
 type HeaderType = 'image' | 'text' | 'none';
 type CreationMode = 'single' | 'all';
 
+// Mirrors `ConnectionStatus` in src-tauri/src/jobs/progress.rs.
+type ConnectionStatus = 'created' | 'skipped_exists' | 'failed' | 'not_attempted' | 'cancelled';
+
+interface ConnectionOutcome {
+  app_id: string;
+  source_name: string | null;
+  status: ConnectionStatus;
+  error: { code: string; message: string } | null;
+  duration_ms: number;
+}
+
 interface BulkResult {
+  job_id: string;
   successful: number;
+  skipped: number;
+  failed: number;
+  not_attempted: number;
+  cancelled: number;
   total: number;
-  app_ids: string[];
+  connections: ConnectionOutcome[];
+}
+
+const CONNECTION_STATUS_LABELS: Record<ConnectionStatus, string> = {
+  created: 'criado',
+  skipped_exists: 'já existe',
+  failed: 'falhou',
+  not_attempted: 'não tentado',
+  cancelled: 'cancelado',
+};
+
+interface LanguageOutcome {
+  language_code: string;
+  status: 'created' | 'already_exists' | 'failed';
+  error: { message: string } | null;
+}
+
+const LANGUAGE_STATUS_LABELS: Record<LanguageOutcome['status'], string> = {
+  created: 'criado',
+  already_exists: 'já existe',
+  failed: 'falhou',
+};
+
+// Mirrors `VariableError` in src-tauri/src/api/variables.rs, sent as the error details.
+interface VariableErrorDetails {
+  kind: 'malformed' | 'not_sequential' | 'too_many_variables' | 'example_count_mismatch' | 'empty_example';
+  part: 'body' | 'header';
+  placeholder?: string;
+  expected?: number;
+  found?: number;
+  max?: number;
+  received?: number;
+  variable?: number;
+  language_code?: string;
+  card?: number;
 }
 
+const PART_LABELS: Record<VariableErrorDetails['part'], string> = {
+  body: 'corpo',
+  header: 'header',
+};
+
+const VARIABLE_PATTERN = /\{\{\s*(\d+)\s*\}\}/g;
+
+// The distinct {{n}} numbers used in the text, in ascending order.
+const variableIndices = (text: string): number[] =>
+  Array.from(new Set(Array.from(text.matchAll(VARIABLE_PATTERN), (match) => Number(match[1]))))
+    .sort((a, b) => a - b);
+
+const fillExamples = (text: string, examples: Record<number, string>) =>
+  text.replace(VARIABLE_PATTERN, (placeholder, index) => examples[Number(index)] || placeholder);
+
+const describeVariableError = (details: VariableErrorDetails): string => {
+  const part = PART_LABELS[details.part];
+  switch (details.kind) {
+    case 'malformed':
+      return `Variável mal formada '${details.placeholder}' no ${part}. Use {{1}}, {{2}}, ...`;
+    case 'not_sequential':
+      return `As variáveis do ${part} devem ser sequenciais: esperado {{${details.expected}}}, encontrado {{${details.found}}}`;
+    case 'too_many_variables':
+      return `O ${part} pode ter no máximo ${details.max} variável(is), encontradas ${details.found}`;
+    case 'example_count_mismatch':
+      return `O ${part} tem ${details.expected} variável(is), mas ${details.received} exemplo(s) foram informados`;
+    case 'empty_example':
+      return `O exemplo da variável {{${details.variable}}} do ${part} está vazio`;
+  }
+};
+
+const describeError = (error: unknown): string => {
+  if (typeof error !== 'object' || error === null || !('message' in error)) {
+    return String(error);
+  }
+  const { message, details } = error as { message: string; details?: VariableErrorDetails | null };
+  if (!details?.kind) {
+    return message;
+  }
+  const where = [
+    details.card ? `card ${details.card}` : null,
+    details.language_code ?? null,
+  ].filter(Boolean).join(', ');
+  return where ? `${describeVariableError(details)} (${where})` : describeVariableError(details);
+};
+
 const TemplateCreator: React.FC = () => {
   const [templateName, setTemplateName] = useState('');
   const [templateType, setTemplateType] = useState<TemplateType>('marketing');
@@ -19,14 +115,20 @@ const TemplateCreator: React.FC = () => {
   const [headerText, setHeaderText] = useState('');
   const [headerImage, setHeaderImage] = useState<string | null>(null);
   const [bodyText, setBodyText] = useState('');
+  const [bodyExamples, setBodyExamples] = useState<Record<number, string>>({});
+  const [headerExamples, setHeaderExamples] = useState<Record<number, string>>({});
   const [imageFile, setImageFile] = useState<File | null>(null);
   const [isSubmitting, setIsSubmitting] = useState(false);
   const [appId, setAppId] = useState('');
   const [vertical, setVertical] = useState('Template');
   const [creationMode, setCreationMode] = useState<CreationMode>('single');
   const [bulkResult, setBulkResult] = useState<BulkResult | null>(null);
+  const [stopOnFailure, setStopOnFailure] = useState(false);
   const [isProcessing, setIsProcessing] = useState<boolean>(false);
 
+  const bodyVariables = variableIndices(bodyText);
+  const headerVariables = headerType === 'text' ? variableIndices(headerText) : [];
+
   const handleImageUpload = (e: React.ChangeEvent<HTMLInputElement>) => {
     const file = e.target.files?.[0];
     if (file) {
@@ -67,6 +169,12 @@ const TemplateCreator: React.FC = () => {
       return;
     }
     
+    if ([...bodyVariables.map((index) => bodyExamples[index]), ...headerVariables.map((index) => headerExamples[index])]
+      .some((example) => !example?.trim())) {
+      alert("Por favor preencha o exemplo de todas as variáveis");
+      return;
+    }
+    
     if (creationMode === 'single' && !appId.trim()) {
       alert("Por favor insira o App ID");
       return;
@@ -85,8 +193,11 @@ const TemplateCreator: React.FC = () => {
         imageName = imageFile.name;
       }
       
+      const examples = bodyVariables.map((index) => bodyExamples[index]);
+      const headerExampleValues = headerType === 'text' ? headerVariables.map((index) => headerExamples[index]) : undefined;
+      
       if (creationMode === 'single') {
-        await invoke('create_template', {
+        const outcomes = await invoke<LanguageOutcome[]>('create_template', {
           params: {
             template_name: templateName,
             app_id: appId,
@@ -95,12 +206,18 @@ const TemplateCreator: React.FC = () => {
             template_type: headerType === 'image' ? 'IMAGE' : 'TEXT',
             vertical,
             header_text: headerType === 'text' ? headerText : undefined,
+            examples,
+            header_examples: headerExampleValues,
             image_data: headerType === 'image' ? imageData : undefined,
             image_name: headerType === 'image' ? imageName : undefined
           }
         });
         
-        alert("Template criado com sucesso!");
+        const lines = outcomes.map((outcome) =>
+          `${outcome.language_code}: ${LANGUAGE_STATUS_LABELS[outcome.status]}${outcome.error ? ` (${outcome.error.message})` : ''}`
+        );
+        const allCreated = outcomes.every((outcome) => outcome.status === 'created');
+        alert(`${allCreated ? 'Template criado com sucesso!' : 'Template criado apenas em parte.'}\n\n${lines.join('\n')}`);
       } else {
         setIsProcessing(true);
         
@@ -112,18 +229,30 @@ const TemplateCreator: React.FC = () => {
             template_type: headerType === 'image' ? 'IMAGE' : 'TEXT',
             vertical,
             header_text: headerType === 'text' ? headerText : undefined,
+            examples,
+            header_examples: headerExampleValues,
             image_data: headerType === 'image' ? imageData : undefined,
-            image_name: headerType === 'image' ? imageName : undefined
+            image_name: headerType === 'image' ? imageName : undefined,
+            continue_on_error: !stopOnFailure
           }
         });
         
         setBulkResult(result);
-        alert(`Templates criados com sucesso para ${result.successful} de ${result.total} conexões.`);
+        const counts = `${result.successful} criados, ${result.skipped} já existiam, ${result.failed} falharam, ` +
+          `${result.not_attempted} não tentados e ${result.cancelled} cancelados de ${result.total} conexões.`;
+        const firstFailure = result.connections.find((connection) => connection.status === 'failed');
+        if (firstFailure) {
+          alert(`Criação concluída com falhas: ${counts}\n\nPrimeira falha (${firstFailure.app_id}): ${firstFailure.error?.message ?? 'erro desconhecido'}`);
+        } else if (result.not_attempted > 0 || result.cancelled > 0) {
+          alert(`Criação interrompida: ${counts}`);
+        } else {
+          alert(`Templates criados com sucesso: ${counts}`);
+        }
       }
       
     } catch (error) {
       console.error('Error creating template:', error);
-      alert(`Erro ao criar template: ${error}`);
+      alert(`Erro ao criar template: ${describeError(error)}`);
     } finally {
       setIsSubmitting(false);
       setIsProcessing(false);
@@ -132,7 +261,7 @@ const TemplateCreator: React.FC = () => {
 
   const formatPreviewText = (text: string) => {
     // Replace variables with example values and convert newlines to <br> tags for HTML display
-    return text
+    return fillExamples(text, bodyExamples)
       .replace(/\{\{name\}\}/g, "John")
       .replace(/\{\{date\}\}/g, new Date().toLocaleDateString())
       .split('\n').map((line, index) => (
@@ -181,9 +310,19 @@ const TemplateCreator: React.FC = () => {
                 </button>
               </div>
               {creationMode === 'all' && (
-                <p className="mode-description">
-                  Este modo criará o template para todas as conexões com App ID válido no banco de dados.
-                </p>
+                <>
+                  <p className="mode-description">
+                    Este modo criará o template para todas as conexões com App ID válido no banco de dados.
+                  </p>
+                  <label className="checkbox-option">
+                    <input
+                      type="checkbox"
+                      checked={stopOnFailure}
+                      onChange={(e) => setStopOnFailure(e.target.checked)}
+                    />
+                    Parar na primeira falha (envia para uma conexão por vez)
+                  </label>
+                </>
               )}
             </div>
 
@@ -249,6 +388,18 @@ const TemplateCreator: React.FC = () => {
                     value={headerText}
                     onChange={(e) => setHeaderText(e.target.value)}
                   />
+                  {headerVariables.map((index) => (
+                    <div className="input-group variable-example" key={index}>
+                      <label htmlFor={`header-example-${index}`}>{`Exemplo de {{${index}}}`}</label>
+                      <input
+                        id={`header-example-${index}`}
+                        type="text"
+                        value={headerExamples[index] ?? ''}
+                        onChange={(e) => setHeaderExamples({ ...headerExamples, [index]: e.target.value })}
+                        placeholder="Valor de exemplo"
+                      />
+                    </div>
+                  ))}
                 </div>
               )}
 
@@ -282,6 +433,22 @@ const TemplateCreator: React.FC = () => {
                 <br />
                 Pressione Enter para adicionar quebras de linha
               </p>
+              {bodyVariables.length > 0 && (
+                <div className="variable-examples">
+                  {bodyVariables.map((index) => (
+                    <div className="input-group" key={index}>
+                      <label htmlFor={`body-example-${index}`}>{`Exemplo de {{${index}}}`}</label>
+                      <input
+                        id={`body-example-${index}`}
+                        type="text"
+                        value={bodyExamples[index] ?? ''}
+                        onChange={(e) => setBodyExamples({ ...bodyExamples, [index]: e.target.value })}
+                        placeholder="Valor de exemplo"
+                      />
+                    </div>
+                  ))}
+                </div>
+              )}
             </div>
 
             <div className="form-group">
@@ -318,12 +485,19 @@ const TemplateCreator: React.FC = () => {
             {bulkResult && (
               <div className="result-summary">
                 <h3>Resultado da Criação</h3>
-                <p>Templates criados: {bulkResult.successful} de {bulkResult.total}</p>
+                <p>
+                  Criados: {bulkResult.successful} · Já existiam: {bulkResult.skipped} · Falharam: {bulkResult.failed}
+                  {' '}· Não tentados: {bulkResult.not_attempted} · Cancelados: {bulkResult.cancelled} · Total: {bulkResult.total}
+                </p>
                 <div className="app-ids-list">
-                  <p>App IDs processados:</p>
+                  <p>Conexões:</p>
                   <ul>
-                    {bulkResult.app_ids.map((id, index) => (
-                      <li key={index}>{id}</li>
+                    {bulkResult.connections.map((connection) => (
+                      <li key={connection.app_id} className={`connection-${connection.status}`}>
+                        {connection.source_name ? `${connection.source_name} (${connection.app_id})` : connection.app_id}
+                        {`: ${CONNECTION_STATUS_LABELS[connection.status]}`}
+                        {connection.error && ` - ${connection.error.code}: ${connection.error.message}`}
+                      </li>
                     ))}
                   </ul>
                 </div>
@@ -365,7 +539,7 @@ const TemplateCreator: React.FC = () => {
                   )}
                   
                   {headerType === 'text' && headerText && (
-                    <div className="message-header-text">{headerText}</div>
+                    <div className="message-header-text">{fillExamples(headerText, headerExamples)}</div>
                   )}
                   
                   <div className="message-body">